@@ -0,0 +1,112 @@
+use std::collections::HashMap;
+use std::{env, fs, path::PathBuf};
+
+#[path = "src/oui/csv.rs"]
+mod csv;
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=src/oui/csv.rs");
+
+    if env::var_os("CARGO_FEATURE_OUI").is_some() {
+        generate_oui_registry();
+    }
+}
+
+/// embeds compacted registry from `data/oui/registry.txt`, or every `.csv` registry file
+/// from `CURUAM_OUI_DIR` if it's set, as a sorted table used by `Mac::vendor`
+fn generate_oui_registry() {
+    println!("cargo:rerun-if-env-changed=CURUAM_OUI_DIR");
+
+    let mut entries: Vec<csv::RegistryEntry> = match env::var_os("CURUAM_OUI_DIR") {
+        Some(dir) => read_csv_dir(&PathBuf::from(dir)),
+        None => {
+            let path: PathBuf = PathBuf::from(env::var_os("CARGO_MANIFEST_DIR").expect("no manifest dir"))
+                .join("data/oui/registry.txt");
+            println!("cargo:rerun-if-changed={}", path.display());
+
+            let text: String = fs::read_to_string(&path)
+                .unwrap_or_else(|err| panic!("can't read {}: {}", path.display(), err));
+
+            parse_compact(&text).unwrap_or_else(|err| panic!("{}: {}", path.display(), err))
+        }
+    };
+
+    entries.sort_by_key(|entry| (entry.0, entry.1));
+    entries.dedup_by(|a, b| a.0 == b.0 && a.1 == b.1);
+
+    // every organization is stored once and entries refer to it by index
+    let mut organizations: Vec<String> = Vec::new();
+    let mut indexes: HashMap<String, u16> = HashMap::new();
+
+    let mut table: String = String::from("pub(crate) static OUI_REGISTRY: &[(u64, u8, u16)] = &[\n");
+    for (prefix, bits, organization) in entries {
+        let index: u16 = *indexes.entry(organization).or_insert_with_key(|organization| {
+            organizations.push(organization.clone());
+            u16::try_from(organizations.len() - 1).expect("too many organizations")
+        });
+
+        table.push_str(&format!("    ({:#014x}, {}, {}),\n", prefix, bits, index));
+    }
+    table.push_str("];\n");
+
+    let mut source: String = String::from("pub(crate) static OUI_ORGANIZATIONS: &[&str] = &[\n");
+    for organization in organizations {
+        source.push_str(&format!("    {:?},\n", organization));
+    }
+    source.push_str("];\n");
+    source.push_str(&table);
+
+    let out: PathBuf = PathBuf::from(env::var_os("OUT_DIR").expect("no out dir")).join("oui_registry.rs");
+    fs::write(&out, source).unwrap_or_else(|err| panic!("can't write {}: {}", out.display(), err));
+}
+
+/// reads every `.csv` registry file from the directory
+fn read_csv_dir(dir: &PathBuf) -> Vec<csv::RegistryEntry> {
+    println!("cargo:rerun-if-changed={}", dir.display());
+
+    let mut paths: Vec<PathBuf> = fs::read_dir(dir)
+        .unwrap_or_else(|err| panic!("can't read oui registry directory {}: {}", dir.display(), err))
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|extension| extension == "csv"))
+        .collect();
+    paths.sort();
+
+    let mut entries: Vec<csv::RegistryEntry> = Vec::new();
+    for path in paths {
+        println!("cargo:rerun-if-changed={}", path.display());
+
+        let text: String = fs::read_to_string(&path)
+            .unwrap_or_else(|err| panic!("can't read {}: {}", path.display(), err));
+        let parsed = csv::parse_registry(&text).unwrap_or_else(|err| panic!("{}: {}", path.display(), err));
+
+        entries.extend(parsed);
+    }
+
+    entries
+}
+
+/// parses registry written by `examples/oui_compact.rs`, every line is organization name,
+/// tab and comma separated hex assignments, lines starting with `#` are comments
+fn parse_compact(text: &str) -> Result<Vec<csv::RegistryEntry>, String> {
+    let mut entries: Vec<csv::RegistryEntry> = Vec::new();
+
+    for (line, record) in text.lines().enumerate() {
+        if record.is_empty() || record.starts_with('#') {
+            continue;
+        }
+
+        let (organization, assignments): (&str, &str) = record
+            .split_once('\t')
+            .ok_or_else(|| format!("line {} has no assignments", line + 1))?;
+
+        for assignment in assignments.split(',') {
+            let (prefix, bits): (u64, u8) = csv::parse_assignment(assignment)
+                .ok_or_else(|| format!("line {} has invalid assignment {:?}", line + 1, assignment))?;
+
+            entries.push((prefix, bits, organization.to_string()));
+        }
+    }
+
+    Ok(entries)
+}
@@ -0,0 +1,49 @@
+//! compacts ieee registry csv files into `data/oui/registry.txt` used by the `oui` feature
+//!
+//! ```text
+//! cargo run --example oui_compact -- data/oui > data/oui/registry.txt
+//! ```
+
+use std::collections::BTreeMap;
+use std::{env, fs, path::PathBuf};
+
+#[path = "../src/oui/csv.rs"]
+mod csv;
+
+fn main() {
+    let dir: PathBuf = env::args_os().nth(1).map(PathBuf::from).unwrap_or_else(|| PathBuf::from("data/oui"));
+
+    let mut paths: Vec<PathBuf> = fs::read_dir(&dir)
+        .unwrap_or_else(|err| panic!("can't read oui registry directory {}: {}", dir.display(), err))
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|extension| extension == "csv"))
+        .collect();
+    paths.sort();
+
+    let mut entries: Vec<csv::RegistryEntry> = Vec::new();
+    for path in paths {
+        let text: String = fs::read_to_string(&path)
+            .unwrap_or_else(|err| panic!("can't read {}: {}", path.display(), err));
+
+        entries.extend(csv::parse_registry(&text).unwrap_or_else(|err| panic!("{}: {}", path.display(), err)));
+    }
+
+    // same deduplication as in build.rs, the first file wins
+    entries.sort_by_key(|entry| (entry.0, entry.1));
+    entries.dedup_by(|a, b| a.0 == b.0 && a.1 == b.1);
+
+    let mut organizations: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for (prefix, bits, organization) in entries {
+        let digits: usize = bits as usize / 4;
+        let assignment: String = format!("{:012X}", prefix)[..digits].to_string();
+
+        organizations.entry(organization).or_default().push(assignment);
+    }
+
+    println!("# ieee MA-L, MA-M and MA-S assignments compacted from oui.csv, mam.csv and oui36.csv");
+    println!("# regenerate with `cargo run --example oui_compact -- data/oui > data/oui/registry.txt`");
+    println!("# every line is organization name, tab and comma separated hex assignments");
+    for (organization, assignments) in organizations {
+        println!("{}\t{}", organization, assignments.join(","));
+    }
+}
@@ -0,0 +1,196 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+
+use crate::{Handle, Ipv4, Ipv4ParseError, Ipv6, Ipv6ParseError, IPV4_LEN, IPV6_LEN};
+
+/// ip address of either version, shares text forms with [`Ipv4`] and [`Ipv6`]
+///
+/// # Example
+/// ```
+/// use curuam::*;
+///
+/// let ip_addr: IpAddress = "192.168.1.1".parse().expect("invalid ip");
+/// let std_ip_addr: std::net::IpAddr = ip_addr.into();
+///
+/// assert!(ip_addr.is_ipv4());
+/// assert_eq!(std_ip_addr.to_string(), ip_addr.to_string());
+/// assert_eq!(ip_addr.to_socket_addr(80).to_string(), "192.168.1.1:80")
+/// ```
+///
+/// # Conversions Example
+/// ```
+/// use curuam::*;
+/// use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+///
+/// let std_ipv4_addr: Ipv4Addr = "192.168.1.1".parse().expect("invalid ip");
+/// let std_ipv6_addr: Ipv6Addr = "2001:db8::1".parse().expect("invalid ip");
+/// let std_ip_addr: IpAddr = "2001:db8::1".parse().expect("invalid ip");
+/// let socket_addr: SocketAddr = "[2001:db8::1]:443".parse().expect("invalid socket address");
+///
+/// let ipv4_addr: Ipv4 = std_ipv4_addr.into();
+/// let ipv6_addr: Ipv6 = std_ipv6_addr.into();
+/// let ip_addr: IpAddress = std_ip_addr.into();
+///
+/// assert_eq!(Ipv4Addr::from(ipv4_addr), std_ipv4_addr);
+/// assert_eq!(Ipv6Addr::from(ipv6_addr), std_ipv6_addr);
+/// assert_eq!(IpAddr::from(ip_addr), std_ip_addr);
+///
+/// // socket address keeps the port, only the ip address is taken from it
+/// let ip_addr: IpAddress = IpAddress::from_socket_ip(&socket_addr);
+/// assert_eq!(ip_addr.to_socket_addr(socket_addr.port()), socket_addr);
+///
+/// // version is chosen by `:`, errors tell which one failed
+/// assert_eq!(
+///     "192.168.1".parse::<IpAddress>(),
+///     Err(IpAddressParseError::Ipv4(Ipv4ParseError::OctetCount(3)))
+/// );
+/// assert!(matches!("2001:db8::g".parse::<IpAddress>(), Err(IpAddressParseError::Ipv6(_))))
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum IpAddress {
+    V4(Ipv4),
+    V6(Ipv6),
+}
+
+/// error returned when parsing ip address of either version from string
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IpAddressParseError {
+    Ipv4(Ipv4ParseError),
+    Ipv6(Ipv6ParseError),
+}
+
+impl IpAddress {
+    pub fn is_ipv4(&self) -> bool {
+        matches!(self, Self::V4(_))
+    }
+    pub fn is_ipv6(&self) -> bool {
+        matches!(self, Self::V6(_))
+    }
+    pub fn to_socket_addr(&self, port: u16) -> SocketAddr {
+        SocketAddr::new((*self).into(), port)
+    }
+    /// ip address of the socket address, port is dropped
+    pub fn from_socket_ip(socket_addr: &SocketAddr) -> Self {
+        socket_addr.ip().into()
+    }
+}
+
+impl Ipv4 {
+    pub fn to_socket_addr(&self, port: u16) -> SocketAddrV4 {
+        SocketAddrV4::new((*self).into(), port)
+    }
+    /// ip address of the socket address, port is dropped
+    pub fn from_socket_ip(socket_addr: &SocketAddrV4) -> Self {
+        (*socket_addr.ip()).into()
+    }
+}
+
+impl Ipv6 {
+    pub fn to_socket_addr(&self, port: u16) -> SocketAddrV6 {
+        SocketAddrV6::new((*self).into(), port, 0, 0)
+    }
+    /// ip address of the socket address, port, flow info and scope id are dropped
+    pub fn from_socket_ip(socket_addr: &SocketAddrV6) -> Self {
+        (*socket_addr.ip()).into()
+    }
+}
+
+impl From<Ipv4Addr> for Ipv4 {
+    fn from(ip_addr: Ipv4Addr) -> Self {
+        Handle::from(ip_addr.octets())
+    }
+}
+
+impl From<Ipv4> for Ipv4Addr {
+    fn from(ip_addr: Ipv4) -> Self {
+        Ipv4Addr::from(Handle::<[u8; IPV4_LEN]>::to(&ip_addr))
+    }
+}
+
+impl From<Ipv6Addr> for Ipv6 {
+    fn from(ip_addr: Ipv6Addr) -> Self {
+        Handle::from(ip_addr.octets())
+    }
+}
+
+impl From<Ipv6> for Ipv6Addr {
+    fn from(ip_addr: Ipv6) -> Self {
+        Ipv6Addr::from(Handle::<[u8; IPV6_LEN]>::to(&ip_addr))
+    }
+}
+
+impl From<Ipv4> for IpAddress {
+    fn from(ip_addr: Ipv4) -> Self {
+        Self::V4(ip_addr)
+    }
+}
+
+impl From<Ipv6> for IpAddress {
+    fn from(ip_addr: Ipv6) -> Self {
+        Self::V6(ip_addr)
+    }
+}
+
+impl From<IpAddr> for IpAddress {
+    fn from(ip_addr: IpAddr) -> Self {
+        match ip_addr {
+            IpAddr::V4(ip_addr) => Self::V4(ip_addr.into()),
+            IpAddr::V6(ip_addr) => Self::V6(ip_addr.into()),
+        }
+    }
+}
+
+impl From<IpAddress> for IpAddr {
+    fn from(ip_addr: IpAddress) -> Self {
+        match ip_addr {
+            IpAddress::V4(ip_addr) => IpAddr::V4(ip_addr.into()),
+            IpAddress::V6(ip_addr) => IpAddr::V6(ip_addr.into()),
+        }
+    }
+}
+
+impl std::fmt::Display for IpAddress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::V4(ip_addr) => ip_addr.fmt(f),
+            Self::V6(ip_addr) => ip_addr.fmt(f),
+        }
+    }
+}
+
+impl std::str::FromStr for IpAddress {
+    type Err = IpAddressParseError;
+
+    /// parses ipv6 address if string contains `:`, otherwise ipv4 address
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.contains(':') {
+            true => s.parse().map(Self::V6).map_err(IpAddressParseError::Ipv6),
+            false => s.parse().map(Self::V4).map_err(IpAddressParseError::Ipv4),
+        }
+    }
+}
+
+impl TryFrom<&str> for IpAddress {
+    type Error = IpAddressParseError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl std::fmt::Display for IpAddressParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Ipv4(err) => write!(f, "invalid ipv4 address: {}", err),
+            Self::Ipv6(err) => write!(f, "invalid ipv6 address: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for IpAddressParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Ipv4(err) => Some(err),
+            Self::Ipv6(err) => Some(err),
+        }
+    }
+}
@@ -0,0 +1,361 @@
+use crate::{
+    ArpHeader, EtherType, FromBytes, Handle, Ipv4, Mac, ParseError, U16Be, ARP_HEADER_SIZE, ETH_HEADER_SIZE, IPV4_LEN,
+    MAC_LEN,
+};
+
+/// length of ethernet frame carrying arp packet, padded with zeros to the 60-byte minimum
+/// ethernet frame length without fcs
+pub const ARP_FRAME_SIZE: usize = 60;
+
+/// length of arp header fields preceding the addresses
+const ARP_FIXED_SIZE: usize = 8;
+
+/// hardware type of arp packet
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ArpHardware {
+    Ethernet,
+    Ieee802,
+    FrameRelay,
+    Atm,
+    Hdlc,
+    FibreChannel,
+    SerialLine,
+    Infiniband,
+    Other(u16),
+}
+
+/// operation of arp packet, including rarp (RFC 903) and inarp (RFC 2390)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ArpOperation {
+    Request,
+    Reply,
+    ReverseRequest,
+    ReverseReply,
+    InverseRequest,
+    InverseReply,
+    Other(u16),
+}
+
+/// zero-copy view of arp packet with addresses of any length
+///
+/// # Example
+/// ```
+/// use curuam::*;
+///
+/// let packet: [u8; 20] = [
+///     0x00, 0x0f, // frame relay
+///     0x08, 0x00, // ipv4
+///     0x02, 0x04, // address lengths
+///     0x00, 0x08, // inarp request
+///     0x04, 0x01, 192, 168, 0, 1, // sender
+///     0x04, 0x02, 0, 0, 0, 0, // target
+/// ];
+///
+/// let (arp, _) = ArpPacket::parse(&packet).expect("invalid arp");
+///
+/// assert_eq!(arp.hardware_type(), ArpHardware::FrameRelay);
+/// assert_eq!(arp.operation(), ArpOperation::InverseRequest);
+/// assert_eq!(arp.sender_hardware(), [0x04, 0x01]);
+/// assert_eq!(arp.sender_ipv4(), Some("192.168.0.1".parse().expect("invalid ip")));
+/// assert!(arp.header().is_none())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArpPacket<'a> {
+    bytes: &'a [u8],
+}
+
+impl ArpHeader {
+    /// creates ethernet/ipv4 arp header
+    pub fn new(operation: ArpOperation, sender_mac: Mac, sender_ip: Ipv4, target_mac: Mac, target_ip: Ipv4) -> Self {
+        Self {
+            hardware_type: U16Be::new(ArpHardware::Ethernet.to()),
+            protocol_type: U16Be::new(EtherType::Ipv4.to()),
+            hardware_len: MAC_LEN as u8,
+            protocol_len: IPV4_LEN as u8,
+            opcode: U16Be::new(operation.to()),
+            sender_mac: Handle::to(&sender_mac),
+            sender_ip: Handle::to(&sender_ip),
+            target_mac: Handle::to(&target_mac),
+            target_ip: Handle::to(&target_ip),
+        }
+    }
+    /// broadcast frame asking who has `target_ip`
+    ///
+    /// # Example
+    /// ```
+    /// use curuam::*;
+    ///
+    /// let mac_addr: Mac = "00:11:22:33:44:55".parse().expect("invalid mac");
+    /// let sender_ip: Ipv4 = "192.168.1.10".parse().expect("invalid ip");
+    /// let target_ip: Ipv4 = "192.168.1.1".parse().expect("invalid ip");
+    ///
+    /// let frame: [u8; ARP_FRAME_SIZE] = ArpHeader::request(mac_addr, sender_ip, target_ip);
+    ///
+    /// let (eth, payload) = EthHeader::parse(&frame).expect("invalid frame");
+    /// let (arp, _) = ArpHeader::parse(payload).expect("invalid arp");
+    ///
+    /// assert_eq!(frame.len(), 60);
+    /// assert!(eth.destination().is_broadcast());
+    /// assert_eq!(eth.ether_type(), EtherType::Arp);
+    /// assert_eq!(arp.operation(), ArpOperation::Request);
+    /// assert_eq!(arp.sender_mac(), mac_addr);
+    /// assert_eq!(arp.target_ip(), target_ip)
+    /// ```
+    pub fn request(sender_mac: Mac, sender_ip: Ipv4, target_ip: Ipv4) -> [u8; ARP_FRAME_SIZE] {
+        Self::new(ArpOperation::Request, sender_mac, sender_ip, Mac::default(), target_ip).frame(sender_mac, broadcast())
+    }
+    /// unicast frame telling `target_mac` that `sender_ip` is at `sender_mac`
+    pub fn reply(sender_mac: Mac, sender_ip: Ipv4, target_mac: Mac, target_ip: Ipv4) -> [u8; ARP_FRAME_SIZE] {
+        Self::new(ArpOperation::Reply, sender_mac, sender_ip, target_mac, target_ip).frame(sender_mac, target_mac)
+    }
+    /// broadcast request with sender and target ip set to `ip`, used to update neighbor caches
+    pub fn gratuitous(mac_addr: Mac, ip: Ipv4) -> [u8; ARP_FRAME_SIZE] {
+        Self::new(ArpOperation::Request, mac_addr, ip, Mac::default(), ip).frame(mac_addr, broadcast())
+    }
+    /// address conflict probe (RFC 5227), sender ip is unspecified
+    ///
+    /// # Example
+    /// ```
+    /// use curuam::*;
+    ///
+    /// let mac_addr: Mac = "00:11:22:33:44:55".parse().expect("invalid mac");
+    /// let ip: Ipv4 = "169.254.1.2".parse().expect("invalid ip");
+    /// let frame: [u8; ARP_FRAME_SIZE] = ArpHeader::probe(mac_addr, ip);
+    ///
+    /// let (arp, _) = ArpHeader::parse(&frame[ETH_HEADER_SIZE..]).expect("invalid arp");
+    ///
+    /// assert!(arp.is_probe());
+    /// assert!(!arp.is_gratuitous());
+    /// assert!(arp.sender_ip().is_unspecified())
+    /// ```
+    pub fn probe(mac_addr: Mac, ip: Ipv4) -> [u8; ARP_FRAME_SIZE] {
+        Self::new(ArpOperation::Request, mac_addr, Ipv4::default(), Mac::default(), ip).frame(mac_addr, broadcast())
+    }
+    /// address announcement (RFC 5227), same as gratuitous request
+    pub fn announce(mac_addr: Mac, ip: Ipv4) -> [u8; ARP_FRAME_SIZE] {
+        Self::gratuitous(mac_addr, ip)
+    }
+    /// wraps header into ethernet frame padded to the minimum length
+    pub fn frame(&self, source: Mac, destination: Mac) -> [u8; ARP_FRAME_SIZE] {
+        let mut frame: [u8; ARP_FRAME_SIZE] = [0; ARP_FRAME_SIZE];
+
+        frame[..MAC_LEN].copy_from_slice(&Handle::<[u8; MAC_LEN]>::to(&destination));
+        frame[MAC_LEN..2 * MAC_LEN].copy_from_slice(&Handle::<[u8; MAC_LEN]>::to(&source));
+        frame[2 * MAC_LEN..ETH_HEADER_SIZE].copy_from_slice(&EtherType::Arp.to().to_be_bytes());
+        frame[ETH_HEADER_SIZE..ETH_HEADER_SIZE + ARP_HEADER_SIZE].copy_from_slice(self.as_bytes());
+
+        frame
+    }
+    /// splits bytes into ethernet/ipv4 arp header and the rest, address types and lengths are
+    /// validated, use [`ArpPacket`] for other types
+    ///
+    /// # Example
+    /// ```
+    /// use curuam::*;
+    ///
+    /// let mac_addr: Mac = "00:11:22:33:44:55".parse().expect("invalid mac");
+    /// let ip: Ipv4 = "192.168.1.10".parse().expect("invalid ip");
+    ///
+    /// let mut packet: Vec<u8> = ArpHeader::gratuitous(mac_addr, ip)[ETH_HEADER_SIZE..].to_vec();
+    /// packet[..2].copy_from_slice(&0x000fu16.to_be_bytes());
+    ///
+    /// assert_eq!(
+    ///     ArpHeader::parse(&packet).map(|_| ()),
+    ///     Err(ParseError::InvalidAddressType { hardware: 0x000f, protocol: 0x0800 })
+    /// )
+    /// ```
+    pub fn parse(bytes: &[u8]) -> Result<(&ArpHeader, &[u8]), ParseError> {
+        let (header, rest): (&ArpHeader, &[u8]) = ArpHeader::ref_from(bytes)?;
+
+        if header.hardware_type() != ArpHardware::Ethernet || header.protocol_type() != EtherType::Ipv4 {
+            return Err(ParseError::InvalidAddressType {
+                hardware: header.hardware_type.get(),
+                protocol: header.protocol_type.get(),
+            });
+        }
+
+        if header.hardware_len as usize != MAC_LEN || header.protocol_len as usize != IPV4_LEN {
+            return Err(ParseError::InvalidAddressLength {
+                hardware: header.hardware_len,
+                protocol: header.protocol_len,
+            });
+        }
+
+        Ok((header, rest))
+    }
+    pub fn hardware_type(&self) -> ArpHardware {
+        Handle::from(self.hardware_type.get())
+    }
+    pub fn protocol_type(&self) -> EtherType {
+        Handle::from(self.protocol_type.get())
+    }
+    pub fn operation(&self) -> ArpOperation {
+        Handle::from(self.opcode.get())
+    }
+    pub fn set_operation(&mut self, operation: ArpOperation) {
+        self.opcode.set(operation.to())
+    }
+    pub fn sender_mac(&self) -> Mac {
+        Handle::from(self.sender_mac)
+    }
+    pub fn sender_ip(&self) -> Ipv4 {
+        Handle::from(self.sender_ip)
+    }
+    pub fn target_mac(&self) -> Mac {
+        Handle::from(self.target_mac)
+    }
+    pub fn target_ip(&self) -> Ipv4 {
+        Handle::from(self.target_ip)
+    }
+    /// checks whether it's gratuitous arp, sender and target ip are the same
+    pub fn is_gratuitous(&self) -> bool {
+        self.sender_ip == self.target_ip && !self.sender_ip().is_unspecified()
+    }
+    /// checks whether it's arp probe, request with unspecified sender ip
+    pub fn is_probe(&self) -> bool {
+        self.operation() == ArpOperation::Request && self.sender_ip().is_unspecified()
+    }
+}
+
+impl<'a> ArpPacket<'a> {
+    /// splits bytes into arp packet and the rest, address lengths are taken from the packet
+    pub fn parse(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), ParseError> {
+        if bytes.len() < ARP_FIXED_SIZE {
+            return Err(ParseError::Truncated {
+                needed: ARP_FIXED_SIZE,
+                available: bytes.len(),
+            });
+        }
+
+        let len: usize = ARP_FIXED_SIZE + 2 * (bytes[4] as usize + bytes[5] as usize);
+
+        if bytes.len() < len {
+            return Err(ParseError::Truncated {
+                needed: len,
+                available: bytes.len(),
+            });
+        }
+
+        let (packet, rest): (&'a [u8], &'a [u8]) = bytes.split_at(len);
+
+        Ok((Self { bytes: packet }, rest))
+    }
+    pub fn hardware_type(&self) -> ArpHardware {
+        Handle::from(u16::from_be_bytes([self.bytes[0], self.bytes[1]]))
+    }
+    pub fn protocol_type(&self) -> EtherType {
+        Handle::from(u16::from_be_bytes([self.bytes[2], self.bytes[3]]))
+    }
+    pub fn hardware_len(&self) -> usize {
+        self.bytes[4] as usize
+    }
+    pub fn protocol_len(&self) -> usize {
+        self.bytes[5] as usize
+    }
+    pub fn operation(&self) -> ArpOperation {
+        Handle::from(u16::from_be_bytes([self.bytes[6], self.bytes[7]]))
+    }
+    pub fn sender_hardware(&self) -> &'a [u8] {
+        self.address(0, self.hardware_len())
+    }
+    pub fn sender_protocol(&self) -> &'a [u8] {
+        self.address(self.hardware_len(), self.protocol_len())
+    }
+    pub fn target_hardware(&self) -> &'a [u8] {
+        self.address(self.hardware_len() + self.protocol_len(), self.hardware_len())
+    }
+    pub fn target_protocol(&self) -> &'a [u8] {
+        self.address(2 * self.hardware_len() + self.protocol_len(), self.protocol_len())
+    }
+    /// sender hardware address if it's 6 bytes long
+    pub fn sender_mac(&self) -> Option<Mac> {
+        <[u8; MAC_LEN]>::try_from(self.sender_hardware()).ok().map(Handle::from)
+    }
+    /// sender protocol address if it's ipv4
+    pub fn sender_ipv4(&self) -> Option<Ipv4> {
+        self.ipv4(self.sender_protocol())
+    }
+    /// target hardware address if it's 6 bytes long
+    pub fn target_mac(&self) -> Option<Mac> {
+        <[u8; MAC_LEN]>::try_from(self.target_hardware()).ok().map(Handle::from)
+    }
+    /// target protocol address if it's ipv4
+    pub fn target_ipv4(&self) -> Option<Ipv4> {
+        self.ipv4(self.target_protocol())
+    }
+    /// returns raw bytes of the packet
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+    /// reinterprets packet as fixed [`ArpHeader`] if it has 6-byte hardware and 4-byte protocol addresses
+    pub fn header(&self) -> Option<&'a ArpHeader> {
+        ArpHeader::parse(self.bytes).ok().map(|(header, _)| header)
+    }
+    fn address(&self, offset: usize, len: usize) -> &'a [u8] {
+        &self.bytes[ARP_FIXED_SIZE + offset..ARP_FIXED_SIZE + offset + len]
+    }
+    fn ipv4(&self, address: &[u8]) -> Option<Ipv4> {
+        match self.protocol_type() {
+            EtherType::Ipv4 => <[u8; IPV4_LEN]>::try_from(address).ok().map(Handle::from),
+            _ => None,
+        }
+    }
+}
+
+fn broadcast() -> Mac {
+    Handle::from([0xff; MAC_LEN])
+}
+
+impl Handle<u16> for ArpHardware {
+    fn from(value: u16) -> Self {
+        match value {
+            1 => Self::Ethernet,
+            6 => Self::Ieee802,
+            15 => Self::FrameRelay,
+            16 => Self::Atm,
+            17 => Self::Hdlc,
+            18 => Self::FibreChannel,
+            20 => Self::SerialLine,
+            32 => Self::Infiniband,
+            value => Self::Other(value),
+        }
+    }
+    fn to(&self) -> u16 {
+        match *self {
+            Self::Ethernet => 1,
+            Self::Ieee802 => 6,
+            Self::FrameRelay => 15,
+            Self::Atm => 16,
+            Self::Hdlc => 17,
+            Self::FibreChannel => 18,
+            Self::SerialLine => 20,
+            Self::Infiniband => 32,
+            Self::Other(value) => value,
+        }
+    }
+}
+
+impl Handle<u16> for ArpOperation {
+    fn from(value: u16) -> Self {
+        match value {
+            1 => Self::Request,
+            2 => Self::Reply,
+            3 => Self::ReverseRequest,
+            4 => Self::ReverseReply,
+            8 => Self::InverseRequest,
+            9 => Self::InverseReply,
+            value => Self::Other(value),
+        }
+    }
+    fn to(&self) -> u16 {
+        match *self {
+            Self::Request => 1,
+            Self::Reply => 2,
+            Self::ReverseRequest => 3,
+            Self::ReverseReply => 4,
+            Self::InverseRequest => 8,
+            Self::InverseReply => 9,
+            Self::Other(value) => value,
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use crate::{Handle, IpProtocol, Ipv4, Ipv6, IPV4_LEN, IPV6_LEN};
+
+/// internet checksum (RFC 1071) accumulated over several byte slices, slices are summed
+/// as if they were concatenated, so pseudo headers can be added piece by piece
+///
+/// # Example
+/// ```
+/// use curuam::*;
+///
+/// let mut checksum = Checksum::new();
+/// checksum.add_bytes(&[0x00, 0x01, 0xf2]).add_bytes(&[0x03, 0xf4, 0xf5, 0xf6, 0xf7]);
+///
+/// assert_eq!(checksum.finish(), 0x220d);
+/// assert_eq!(Checksum::compute(&[0x00, 0x01, 0xf2, 0x03, 0xf4, 0xf5, 0xf6, 0xf7]), 0x220d)
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Checksum {
+    sum: u64,
+    odd: Option<u8>,
+}
+
+impl Checksum {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// computes checksum of a single buffer
+    pub fn compute(bytes: &[u8]) -> u16 {
+        Self::new().add_bytes(bytes).finish()
+    }
+    pub fn add_bytes(&mut self, bytes: &[u8]) -> &mut Self {
+        let mut bytes: &[u8] = bytes;
+
+        if let (Some(high), Some((&low, rest))) = (self.odd, bytes.split_first()) {
+            self.sum += u16::from_be_bytes([high, low]) as u64;
+            self.odd = None;
+            bytes = rest;
+        }
+
+        let mut words = bytes.chunks_exact(2);
+        for word in &mut words {
+            self.sum += u16::from_be_bytes([word[0], word[1]]) as u64;
+        }
+
+        if let [byte] = words.remainder() {
+            self.odd = Some(*byte);
+        }
+
+        self
+    }
+    pub fn add_u16(&mut self, value: u16) -> &mut Self {
+        self.add_bytes(&value.to_be_bytes())
+    }
+    pub fn add_u32(&mut self, value: u32) -> &mut Self {
+        self.add_bytes(&value.to_be_bytes())
+    }
+    /// adds ipv4 pseudo header used by udp and tcp checksums, `len` is the upper-layer length
+    pub fn add_ipv4_pseudo_header(
+        &mut self,
+        source: &Ipv4,
+        destination: &Ipv4,
+        protocol: IpProtocol,
+        len: u16,
+    ) -> &mut Self {
+        self.add_bytes(&Handle::<[u8; IPV4_LEN]>::to(source))
+            .add_bytes(&Handle::<[u8; IPV4_LEN]>::to(destination))
+            .add_u16(protocol.to() as u16)
+            .add_u16(len)
+    }
+    /// adds ipv6 pseudo header (RFC 8200 section 8.1), `len` is the upper-layer length
+    pub fn add_ipv6_pseudo_header(
+        &mut self,
+        source: &Ipv6,
+        destination: &Ipv6,
+        next: IpProtocol,
+        len: u32,
+    ) -> &mut Self {
+        self.add_bytes(&Handle::<[u8; IPV6_LEN]>::to(source))
+            .add_bytes(&Handle::<[u8; IPV6_LEN]>::to(destination))
+            .add_u32(len)
+            .add_u32(next.to() as u32)
+    }
+    /// returns ones' complement of the folded sum, ready to be stored in a header
+    pub fn finish(&self) -> u16 {
+        let mut sum: u64 = self.sum;
+        if let Some(byte) = self.odd {
+            sum += (byte as u64) << 8;
+        }
+
+        while sum >> 16 != 0 {
+            sum = (sum & 0xffff) + (sum >> 16);
+        }
+
+        !(sum as u16)
+    }
+}
@@ -0,0 +1,71 @@
+/// 16-bit integer stored in network byte order, memory layout matches the wire format
+///
+/// # Example
+/// ```
+/// use curuam::*;
+///
+/// let mut value = U16Be::new(0x0800);
+///
+/// assert_eq!(value.to_bytes(), [0x08, 0x00]);
+///
+/// value.set(0x86dd);
+///
+/// assert_eq!(value.get(), 0x86dd)
+/// ```
+#[repr(transparent)]
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct U16Be([u8; 2]);
+
+/// 32-bit integer stored in network byte order, memory layout matches the wire format
+#[repr(transparent)]
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct U32Be([u8; 4]);
+
+macro_rules! impl_big_endian {
+    ($type:ident, $int:ty, $len:expr) => {
+        impl $type {
+            pub const fn new(value: $int) -> Self {
+                Self(value.to_be_bytes())
+            }
+            pub const fn from_bytes(bytes: [u8; $len]) -> Self {
+                Self(bytes)
+            }
+            pub const fn get(&self) -> $int {
+                <$int>::from_be_bytes(self.0)
+            }
+            pub fn set(&mut self, value: $int) {
+                self.0 = value.to_be_bytes()
+            }
+            pub const fn to_bytes(&self) -> [u8; $len] {
+                self.0
+            }
+        }
+
+        impl From<$int> for $type {
+            fn from(value: $int) -> Self {
+                Self::new(value)
+            }
+        }
+
+        impl From<$type> for $int {
+            fn from(value: $type) -> Self {
+                value.get()
+            }
+        }
+
+        impl std::fmt::Debug for $type {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                std::fmt::Debug::fmt(&self.get(), f)
+            }
+        }
+
+        impl std::fmt::Display for $type {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                std::fmt::Display::fmt(&self.get(), f)
+            }
+        }
+    };
+}
+
+impl_big_endian!(U16Be, u16, 2);
+impl_big_endian!(U32Be, u32, 4);
@@ -0,0 +1,361 @@
+use crate::{EthHeader, FromBytes, Handle, Mac, ParseError, U16Be, ETH_HEADER_SIZE, MAC_LEN};
+
+/// length of vlan tag including its tpid
+pub const VLAN_TAG_SIZE: usize = 4;
+
+const MAX_VID: u16 = 0x0fff;
+const MAX_PCP: u8 = 0x07;
+
+/// ethertype of ethernet frame payload
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EtherType {
+    Ipv4,
+    Arp,
+    Rarp,
+    /// 802.1Q vlan tag
+    Vlan,
+    /// 802.1ad service vlan tag
+    QinQ,
+    Ipv6,
+    Mpls,
+    PppoeDiscovery,
+    PppoeSession,
+    Lldp,
+    Other(u16),
+}
+
+/// zero-copy view of ethernet header borrowed from a byte slice
+///
+/// # Example
+/// ```
+/// use curuam::*;
+///
+/// let frame: [u8; 16] = [
+///     0xff, 0xff, 0xff, 0xff, 0xff, 0xff, // destination
+///     0x00, 0x11, 0x22, 0x33, 0x44, 0x55, // source
+///     0x08, 0x06, // ethertype
+///     0xde, 0xad, // payload
+/// ];
+///
+/// let (header, payload) = EthHeader::parse(&frame).expect("invalid frame");
+///
+/// assert!(header.destination().is_broadcast());
+/// assert_eq!(header.source().to_string(), "00:11:22:33:44:55");
+/// assert_eq!(header.ether_type(), EtherType::Arp);
+/// assert_eq!(payload, [0xde, 0xad])
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EthHeaderView<'a> {
+    bytes: &'a [u8],
+}
+
+/// 802.1Q or 802.1ad vlan tag, fields are kept in range so that the tag can be read back
+///
+/// # Example
+/// ```
+/// use curuam::*;
+///
+/// let tag: VlanTag = VlanTag::new(100)
+///     .and_then(|tag| tag.with_priority(5, true))
+///     .expect("invalid tag");
+///
+/// assert_eq!(tag.to_bytes(), [0x81, 0x00, 0xb0, 0x64]);
+/// assert_eq!(VlanTag::from_bytes(tag.to_bytes()), Some(tag));
+/// assert_eq!(VlanTag::new(5000), None);
+/// assert_eq!(VlanTag::from_bytes([0x08, 0x00, 0x00, 0x64]), None)
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct VlanTag {
+    tpid: EtherType,
+    pcp: u8,
+    dei: bool,
+    vid: u16,
+}
+
+/// iterator over vlan tag stack from outermost to innermost
+///
+/// # Example
+/// ```
+/// use curuam::*;
+///
+/// let mut frame: Vec<u8> = vec![0; ETH_HEADER_SIZE];
+/// frame[2 * MAC_LEN..].copy_from_slice(&[0x08, 0x00]);
+///
+/// let customer: VlanTag = VlanTag::new(100).expect("invalid vid");
+/// let service: VlanTag = VlanTag::service(10).expect("invalid vid");
+///
+/// EthHeader::push_vlan(&mut frame, customer).expect("invalid frame");
+/// EthHeader::push_vlan(&mut frame, service).expect("invalid frame");
+///
+/// let (header, tags, ether_type, payload) = EthHeader::parse_tagged(&frame).expect("invalid frame");
+/// let tags: Vec<VlanTag> = tags.collect();
+///
+/// assert_eq!(header.ether_type(), EtherType::QinQ);
+/// assert_eq!(tags, [service, customer]);
+/// assert_eq!(ether_type, EtherType::Ipv4);
+/// assert!(payload.is_empty());
+///
+/// assert_eq!(EthHeader::pop_vlan(&mut frame), Ok(Some(service)));
+/// assert_eq!(frame.len(), ETH_HEADER_SIZE + VLAN_TAG_SIZE)
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VlanTags<'a> {
+    bytes: &'a [u8],
+}
+
+impl EthHeader {
+    /// splits frame into ethernet header view and payload
+    pub fn parse(frame: &[u8]) -> Result<(EthHeaderView<'_>, &[u8]), ParseError> {
+        if frame.len() < ETH_HEADER_SIZE {
+            return Err(ParseError::Truncated {
+                needed: ETH_HEADER_SIZE,
+                available: frame.len(),
+            });
+        }
+
+        let (header, payload): (&[u8], &[u8]) = frame.split_at(ETH_HEADER_SIZE);
+
+        Ok((EthHeaderView { bytes: header }, payload))
+    }
+    pub fn ether_type(&self) -> EtherType {
+        Handle::from(self.proto.get())
+    }
+    /// splits frame into ethernet header view, vlan tag stack, inner ethertype and payload
+    pub fn parse_tagged(frame: &[u8]) -> Result<(EthHeaderView<'_>, VlanTags<'_>, EtherType, &[u8]), ParseError> {
+        let (header, _): (EthHeaderView, &[u8]) = Self::parse(frame)?;
+        let len: usize = tags_len(frame)?;
+
+        let tags: VlanTags = VlanTags {
+            bytes: &frame[2 * MAC_LEN..2 * MAC_LEN + len],
+        };
+        let ether_type: EtherType = ether_type_at(frame, 2 * MAC_LEN + len);
+
+        Ok((header, tags, ether_type, &frame[ETH_HEADER_SIZE + len..]))
+    }
+    /// inserts tag as the outermost one
+    pub fn push_vlan(frame: &mut Vec<u8>, tag: VlanTag) -> Result<(), ParseError> {
+        Self::parse(frame)?;
+
+        frame.splice(2 * MAC_LEN..2 * MAC_LEN, tag.to_bytes());
+
+        Ok(())
+    }
+    /// removes the outermost tag, frame is unchanged if it isn't tagged
+    pub fn pop_vlan(frame: &mut Vec<u8>) -> Result<Option<VlanTag>, ParseError> {
+        let tag: Option<VlanTag> = Self::parse_tagged(frame)?.1.next();
+
+        if tag.is_some() {
+            frame.drain(2 * MAC_LEN..2 * MAC_LEN + VLAN_TAG_SIZE);
+        }
+
+        Ok(tag)
+    }
+    /// replaces tag at `index` counting from the outermost one and returns the previous tag,
+    /// frame is unchanged if there is no such tag
+    pub fn set_vlan(frame: &mut [u8], index: usize, tag: VlanTag) -> Result<Option<VlanTag>, ParseError> {
+        let previous: Option<VlanTag> = Self::parse_tagged(frame)?.1.nth(index);
+
+        if previous.is_some() {
+            let offset: usize = 2 * MAC_LEN + index * VLAN_TAG_SIZE;
+            frame[offset..offset + VLAN_TAG_SIZE].copy_from_slice(&tag.to_bytes());
+        }
+
+        Ok(previous)
+    }
+}
+
+impl VlanTag {
+    /// creates 802.1Q customer tag with zero priority, `None` if vid doesn't fit into 12 bits
+    pub fn new(vid: u16) -> Option<Self> {
+        if vid > MAX_VID {
+            return None;
+        }
+
+        Some(Self {
+            tpid: EtherType::Vlan,
+            pcp: 0,
+            dei: false,
+            vid,
+        })
+    }
+    /// creates 802.1ad service tag with zero priority, `None` if vid doesn't fit into 12 bits
+    pub fn service(vid: u16) -> Option<Self> {
+        Some(Self {
+            tpid: EtherType::QinQ,
+            ..Self::new(vid)?
+        })
+    }
+    /// sets priority code point and drop eligible indicator, `None` if pcp doesn't fit into 3 bits
+    pub fn with_priority(self, pcp: u8, dei: bool) -> Option<Self> {
+        if pcp > MAX_PCP {
+            return None;
+        }
+
+        Some(Self { pcp, dei, ..self })
+    }
+    /// reads tag from tpid and tag control information, `None` if tpid isn't a vlan ethertype
+    pub fn from_bytes(bytes: [u8; VLAN_TAG_SIZE]) -> Option<Self> {
+        let tpid: EtherType = Handle::from(u16::from_be_bytes([bytes[0], bytes[1]]));
+        let tci: u16 = u16::from_be_bytes([bytes[2], bytes[3]]);
+
+        match tpid {
+            EtherType::Vlan | EtherType::QinQ => Some(Self {
+                tpid,
+                pcp: (tci >> 13) as u8,
+                dei: tci & 0x1000 != 0,
+                vid: tci & MAX_VID,
+            }),
+            _ => None,
+        }
+    }
+    /// tag protocol identifier, [`EtherType::Vlan`] or [`EtherType::QinQ`]
+    pub fn tpid(&self) -> EtherType {
+        self.tpid
+    }
+    /// priority code point
+    pub fn pcp(&self) -> u8 {
+        self.pcp
+    }
+    /// drop eligible indicator
+    pub fn dei(&self) -> bool {
+        self.dei
+    }
+    /// vlan identifier
+    pub fn vid(&self) -> u16 {
+        self.vid
+    }
+    /// tag control information
+    pub fn tci(&self) -> u16 {
+        ((self.pcp as u16) << 13) | ((self.dei as u16) << 12) | self.vid
+    }
+    pub fn to_bytes(&self) -> [u8; VLAN_TAG_SIZE] {
+        let tpid: [u8; 2] = self.tpid.to().to_be_bytes();
+        let tci: [u8; 2] = self.tci().to_be_bytes();
+
+        [tpid[0], tpid[1], tci[0], tci[1]]
+    }
+}
+
+impl<'a> VlanTags<'a> {
+    /// returns raw bytes of the remaining tags
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+}
+
+impl Iterator for VlanTags<'_> {
+    type Item = VlanTag;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (tag, rest): (&[u8], &[u8]) = self.bytes.split_at_checked(VLAN_TAG_SIZE)?;
+        self.bytes = rest;
+
+        // tags are only collected while tpid is a vlan ethertype
+        VlanTag::from_bytes([tag[0], tag[1], tag[2], tag[3]])
+    }
+}
+
+/// length of vlan tag stack following source mac
+fn tags_len(frame: &[u8]) -> Result<usize, ParseError> {
+    let mut len: usize = 0;
+
+    while let EtherType::Vlan | EtherType::QinQ = ether_type_at(frame, 2 * MAC_LEN + len) {
+        len += VLAN_TAG_SIZE;
+
+        if frame.len() < ETH_HEADER_SIZE + len {
+            return Err(ParseError::Truncated {
+                needed: ETH_HEADER_SIZE + len,
+                available: frame.len(),
+            });
+        }
+    }
+
+    Ok(len)
+}
+
+fn ether_type_at(bytes: &[u8], offset: usize) -> EtherType {
+    Handle::from(u16::from_be_bytes([bytes[offset], bytes[offset + 1]]))
+}
+
+impl<'a> EthHeaderView<'a> {
+    pub fn destination(&self) -> Mac {
+        mac_at(self.bytes, 0)
+    }
+    pub fn source(&self) -> Mac {
+        mac_at(self.bytes, MAC_LEN)
+    }
+    pub fn ether_type(&self) -> EtherType {
+        ether_type_at(self.bytes, 2 * MAC_LEN)
+    }
+    /// returns raw bytes of the header
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+    /// reinterprets header bytes as [`EthHeader`] without copying
+    ///
+    /// # Example
+    /// ```
+    /// use curuam::*;
+    ///
+    /// let frame: [u8; ETH_HEADER_SIZE] = [0xff; ETH_HEADER_SIZE];
+    ///
+    /// let (view, _) = EthHeader::parse(&frame).expect("invalid frame");
+    /// let header: &EthHeader = view.header();
+    ///
+    /// assert_eq!(header.proto.get(), 0xffff)
+    /// ```
+    pub fn header(&self) -> &'a EthHeader {
+        match EthHeader::ref_from(self.bytes) {
+            Ok((header, _)) => header,
+            Err(_) => unreachable!("length is checked by `EthHeader::parse`"),
+        }
+    }
+    /// copies header into [`EthHeader`]
+    pub fn to_header(&self) -> EthHeader {
+        EthHeader {
+            dest: Handle::to(&self.destination()),
+            source: Handle::to(&self.source()),
+            proto: U16Be::from_bytes([self.bytes[2 * MAC_LEN], self.bytes[2 * MAC_LEN + 1]]),
+        }
+    }
+}
+
+fn mac_at(bytes: &[u8], offset: usize) -> Mac {
+    let mut mac_addr: [u8; MAC_LEN] = [0; MAC_LEN];
+    mac_addr.copy_from_slice(&bytes[offset..offset + MAC_LEN]);
+
+    Handle::from(mac_addr)
+}
+
+impl Handle<u16> for EtherType {
+    fn from(value: u16) -> Self {
+        match value {
+            0x0800 => Self::Ipv4,
+            0x0806 => Self::Arp,
+            0x8035 => Self::Rarp,
+            0x8100 => Self::Vlan,
+            0x88a8 => Self::QinQ,
+            0x86dd => Self::Ipv6,
+            0x8847 => Self::Mpls,
+            0x8863 => Self::PppoeDiscovery,
+            0x8864 => Self::PppoeSession,
+            0x88cc => Self::Lldp,
+            value => Self::Other(value),
+        }
+    }
+    fn to(&self) -> u16 {
+        match *self {
+            Self::Ipv4 => 0x0800,
+            Self::Arp => 0x0806,
+            Self::Rarp => 0x8035,
+            Self::Vlan => 0x8100,
+            Self::QinQ => 0x88a8,
+            Self::Ipv6 => 0x86dd,
+            Self::Mpls => 0x8847,
+            Self::PppoeDiscovery => 0x8863,
+            Self::PppoeSession => 0x8864,
+            Self::Lldp => 0x88cc,
+            Self::Other(value) => value,
+        }
+    }
+}
@@ -0,0 +1,356 @@
+use crate::{u32_at, BuildError, Checksum, FromBytes, Handle, IcmpHeader, Ipv4, Ipv4Header, ParseError, ICMP_HEADER_SIZE};
+
+const TIMESTAMP_SIZE: usize = ICMP_HEADER_SIZE + 12;
+
+/// icmpv4 message borrowed from packet
+///
+/// # Example
+/// ```
+/// use curuam::*;
+///
+/// let request = IcmpMessage::EchoRequest {
+///     id: 0x1234,
+///     sequence: 1,
+///     data: b"ping",
+/// };
+///
+/// let bytes: Vec<u8> = request.to_bytes();
+///
+/// assert!(IcmpMessage::verify_checksum(&bytes));
+/// assert_eq!(IcmpMessage::parse(&bytes).expect("invalid message"), request)
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IcmpMessage<'a> {
+    EchoReply { id: u16, sequence: u16, data: &'a [u8] },
+    DestinationUnreachable {
+        code: UnreachableCode,
+        /// mtu of the next hop for [`UnreachableCode::FragmentationNeeded`] (RFC 1191)
+        next_hop_mtu: u16,
+        /// quoted original ip header and the beginning of its payload
+        original: &'a [u8],
+    },
+    Redirect { code: RedirectCode, gateway: Ipv4, original: &'a [u8] },
+    EchoRequest { id: u16, sequence: u16, data: &'a [u8] },
+    TimeExceeded { code: TimeExceededCode, original: &'a [u8] },
+    ParameterProblem { code: u8, pointer: u8, original: &'a [u8] },
+    Timestamp { id: u16, sequence: u16, originate: u32, receive: u32, transmit: u32 },
+    TimestampReply { id: u16, sequence: u16, originate: u32, receive: u32, transmit: u32 },
+    /// message of other type, `rest` is the second half of the header
+    Other { type_: u8, code: u8, rest: [u8; 4], data: &'a [u8] },
+}
+
+/// code of destination unreachable message
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UnreachableCode {
+    Network,
+    Host,
+    Protocol,
+    Port,
+    FragmentationNeeded,
+    SourceRouteFailed,
+    NetworkUnknown,
+    HostUnknown,
+    SourceHostIsolated,
+    NetworkProhibited,
+    HostProhibited,
+    NetworkTos,
+    HostTos,
+    CommunicationProhibited,
+    HostPrecedenceViolation,
+    PrecedenceCutoff,
+    Other(u8),
+}
+
+/// code of redirect message
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RedirectCode {
+    Network,
+    Host,
+    TosNetwork,
+    TosHost,
+    Other(u8),
+}
+
+/// code of time exceeded message
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TimeExceededCode {
+    TtlExceeded,
+    FragmentReassembly,
+    Other(u8),
+}
+
+impl<'a> IcmpMessage<'a> {
+    /// parses message from bytes starting with icmp header, checksum is not verified
+    pub fn parse(bytes: &'a [u8]) -> Result<Self, ParseError> {
+        let (header, data): (&IcmpHeader, &'a [u8]) = IcmpHeader::ref_from(bytes)?;
+        let rest: [u8; 4] = [bytes[4], bytes[5], bytes[6], bytes[7]];
+        let (id, sequence): (u16, u16) = (header.id.get(), header.sq.get());
+
+        Ok(match header.type_ {
+            0 => Self::EchoReply { id, sequence, data },
+            3 => Self::DestinationUnreachable {
+                code: Handle::from(header.code),
+                next_hop_mtu: sequence,
+                original: data,
+            },
+            5 => Self::Redirect {
+                code: Handle::from(header.code),
+                gateway: Handle::from(rest),
+                original: data,
+            },
+            8 => Self::EchoRequest { id, sequence, data },
+            11 => Self::TimeExceeded {
+                code: Handle::from(header.code),
+                original: data,
+            },
+            12 => Self::ParameterProblem {
+                code: header.code,
+                pointer: rest[0],
+                original: data,
+            },
+            13 | 14 => {
+                if bytes.len() < TIMESTAMP_SIZE {
+                    return Err(ParseError::Truncated {
+                        needed: TIMESTAMP_SIZE,
+                        available: bytes.len(),
+                    });
+                }
+
+                let (originate, receive, transmit): (u32, u32, u32) =
+                    (u32_at(data, 0), u32_at(data, 4), u32_at(data, 8));
+
+                match header.type_ {
+                    13 => Self::Timestamp { id, sequence, originate, receive, transmit },
+                    _ => Self::TimestampReply { id, sequence, originate, receive, transmit },
+                }
+            }
+            type_ => Self::Other {
+                type_,
+                code: header.code,
+                rest,
+                data,
+            },
+        })
+    }
+    /// checks that checksum over the whole message is valid
+    pub fn verify_checksum(bytes: &[u8]) -> bool {
+        bytes.len() >= ICMP_HEADER_SIZE && Checksum::compute(bytes) == 0
+    }
+    pub fn type_(&self) -> u8 {
+        match self {
+            Self::EchoReply { .. } => 0,
+            Self::DestinationUnreachable { .. } => 3,
+            Self::Redirect { .. } => 5,
+            Self::EchoRequest { .. } => 8,
+            Self::TimeExceeded { .. } => 11,
+            Self::ParameterProblem { .. } => 12,
+            Self::Timestamp { .. } => 13,
+            Self::TimestampReply { .. } => 14,
+            Self::Other { type_, .. } => *type_,
+        }
+    }
+    pub fn code(&self) -> u8 {
+        match self {
+            Self::DestinationUnreachable { code, .. } => code.to(),
+            Self::Redirect { code, .. } => code.to(),
+            Self::TimeExceeded { code, .. } => code.to(),
+            Self::ParameterProblem { code, .. } | Self::Other { code, .. } => *code,
+            _ => 0,
+        }
+    }
+    /// returns ip header quoted by error message and the bytes following it
+    ///
+    /// # Example
+    /// ```
+    /// use curuam::*;
+    ///
+    /// let source: Ipv4 = "10.0.0.1".parse().expect("invalid ip");
+    /// let destination: Ipv4 = "10.0.0.2".parse().expect("invalid ip");
+    ///
+    /// let original: Vec<u8> = Ipv4Header::builder(source, destination, IpProtocol::Udp)
+    ///     .payload(&[0x30, 0x39, 0x00, 0x35, 0x00, 0x08, 0x00, 0x00])
+    ///     .build()
+    ///     .expect("packet is too large");
+    ///
+    /// let unreachable = IcmpMessage::DestinationUnreachable {
+    ///     code: UnreachableCode::Port,
+    ///     next_hop_mtu: 0,
+    ///     original: &original,
+    /// };
+    ///
+    /// let (header, payload) = unreachable.original_header().expect("no original header");
+    ///
+    /// assert_eq!(header.destination(), destination);
+    /// assert_eq!(payload.len(), 8)
+    /// ```
+    pub fn original_header(&self) -> Option<(&'a Ipv4Header, &'a [u8])> {
+        let original: &'a [u8] = match self {
+            Self::DestinationUnreachable { original, .. }
+            | Self::Redirect { original, .. }
+            | Self::TimeExceeded { original, .. }
+            | Self::ParameterProblem { original, .. } => original,
+            _ => return None,
+        };
+
+        let (header, _): (&Ipv4Header, &[u8]) = Ipv4Header::ref_from(original).ok()?;
+        header.validate().ok()?;
+
+        let header_len: usize = header.header_len_bytes();
+
+        Some((header, original.get(header_len..)?))
+    }
+    /// length of encoded message
+    pub fn encoded_len(&self) -> usize {
+        match self {
+            Self::Timestamp { .. } | Self::TimestampReply { .. } => TIMESTAMP_SIZE,
+            Self::EchoReply { data, .. } | Self::EchoRequest { data, .. } | Self::Other { data, .. } => {
+                ICMP_HEADER_SIZE + data.len()
+            }
+            Self::DestinationUnreachable { original, .. }
+            | Self::Redirect { original, .. }
+            | Self::TimeExceeded { original, .. }
+            | Self::ParameterProblem { original, .. } => ICMP_HEADER_SIZE + original.len(),
+        }
+    }
+    /// writes message with checksum into the buffer and returns its length
+    pub fn write(&self, buffer: &mut [u8]) -> Result<usize, BuildError> {
+        let len: usize = self.encoded_len();
+        if buffer.len() < len {
+            return Err(BuildError::BufferTooSmall {
+                needed: len,
+                available: buffer.len(),
+            });
+        }
+
+        let buffer: &mut [u8] = &mut buffer[..len];
+        buffer[0] = self.type_();
+        buffer[1] = self.code();
+        buffer[2..4].fill(0);
+
+        let (rest, data): ([u8; 4], &[u8]) = match *self {
+            Self::EchoReply { id, sequence, data } | Self::EchoRequest { id, sequence, data } => {
+                (pair(id, sequence), data)
+            }
+            Self::DestinationUnreachable { next_hop_mtu, original, .. } => (pair(0, next_hop_mtu), original),
+            Self::Redirect { gateway, original, .. } => (gateway.to(), original),
+            Self::TimeExceeded { original, .. } => ([0; 4], original),
+            Self::ParameterProblem { pointer, original, .. } => ([pointer, 0, 0, 0], original),
+            Self::Timestamp { id, sequence, originate, receive, transmit }
+            | Self::TimestampReply { id, sequence, originate, receive, transmit } => {
+                buffer[ICMP_HEADER_SIZE..ICMP_HEADER_SIZE + 4].copy_from_slice(&originate.to_be_bytes());
+                buffer[ICMP_HEADER_SIZE + 4..ICMP_HEADER_SIZE + 8].copy_from_slice(&receive.to_be_bytes());
+                buffer[ICMP_HEADER_SIZE + 8..TIMESTAMP_SIZE].copy_from_slice(&transmit.to_be_bytes());
+
+                (pair(id, sequence), &[])
+            }
+            Self::Other { rest, data, .. } => (rest, data),
+        };
+
+        buffer[4..ICMP_HEADER_SIZE].copy_from_slice(&rest);
+        if !data.is_empty() {
+            buffer[ICMP_HEADER_SIZE..].copy_from_slice(data);
+        }
+
+        let check: u16 = Checksum::compute(buffer);
+        buffer[2..4].copy_from_slice(&check.to_be_bytes());
+
+        Ok(len)
+    }
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes: Vec<u8> = vec![0; self.encoded_len()];
+        match self.write(&mut bytes) {
+            Ok(_) => bytes,
+            Err(_) => unreachable!("buffer has the message length"),
+        }
+    }
+}
+
+fn pair(high: u16, low: u16) -> [u8; 4] {
+    let [a, b]: [u8; 2] = high.to_be_bytes();
+    let [c, d]: [u8; 2] = low.to_be_bytes();
+
+    [a, b, c, d]
+}
+
+impl Handle<u8> for UnreachableCode {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => Self::Network,
+            1 => Self::Host,
+            2 => Self::Protocol,
+            3 => Self::Port,
+            4 => Self::FragmentationNeeded,
+            5 => Self::SourceRouteFailed,
+            6 => Self::NetworkUnknown,
+            7 => Self::HostUnknown,
+            8 => Self::SourceHostIsolated,
+            9 => Self::NetworkProhibited,
+            10 => Self::HostProhibited,
+            11 => Self::NetworkTos,
+            12 => Self::HostTos,
+            13 => Self::CommunicationProhibited,
+            14 => Self::HostPrecedenceViolation,
+            15 => Self::PrecedenceCutoff,
+            value => Self::Other(value),
+        }
+    }
+    fn to(&self) -> u8 {
+        match *self {
+            Self::Network => 0,
+            Self::Host => 1,
+            Self::Protocol => 2,
+            Self::Port => 3,
+            Self::FragmentationNeeded => 4,
+            Self::SourceRouteFailed => 5,
+            Self::NetworkUnknown => 6,
+            Self::HostUnknown => 7,
+            Self::SourceHostIsolated => 8,
+            Self::NetworkProhibited => 9,
+            Self::HostProhibited => 10,
+            Self::NetworkTos => 11,
+            Self::HostTos => 12,
+            Self::CommunicationProhibited => 13,
+            Self::HostPrecedenceViolation => 14,
+            Self::PrecedenceCutoff => 15,
+            Self::Other(value) => value,
+        }
+    }
+}
+
+impl Handle<u8> for RedirectCode {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => Self::Network,
+            1 => Self::Host,
+            2 => Self::TosNetwork,
+            3 => Self::TosHost,
+            value => Self::Other(value),
+        }
+    }
+    fn to(&self) -> u8 {
+        match *self {
+            Self::Network => 0,
+            Self::Host => 1,
+            Self::TosNetwork => 2,
+            Self::TosHost => 3,
+            Self::Other(value) => value,
+        }
+    }
+}
+
+impl Handle<u8> for TimeExceededCode {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => Self::TtlExceeded,
+            1 => Self::FragmentReassembly,
+            value => Self::Other(value),
+        }
+    }
+    fn to(&self) -> u8 {
+        match *self {
+            Self::TtlExceeded => 0,
+            Self::FragmentReassembly => 1,
+            Self::Other(value) => value,
+        }
+    }
+}
@@ -0,0 +1,538 @@
+use crate::{
+    u32_at, BuildError, Checksum, Handle, IpProtocol, Ipv6, Ipv6Header, Mac, ParseError, TimeExceededCode, IPV6_LEN,
+    MAC_LEN,
+};
+
+const ICMPV6_HEADER_SIZE: usize = 4;
+/// longest fixed part of message after checksum, in redirect message
+const MAX_BODY_SIZE: usize = 36;
+/// length field of neighbor discovery option counts 8-byte units
+const MAX_ND_OPTION_SIZE: usize = u8::MAX as usize * 8;
+
+/// icmpv6 message (RFC 4443) including neighbor discovery messages (RFC 4861)
+///
+/// # Example
+/// ```
+/// use curuam::*;
+///
+/// let source: Ipv6 = "fe80::1".parse().expect("invalid ip");
+/// let destination: Ipv6 = "ff02::1:ff00:2".parse().expect("invalid ip");
+/// let target: Ipv6 = "fe80::2".parse().expect("invalid ip");
+/// let mac_addr: Mac = "00:11:22:33:44:55".parse().expect("invalid mac");
+///
+/// let options: Vec<u8> = NdOption::encode(&[NdOption::SourceLinkLayerAddress(mac_addr)]).expect("invalid option");
+/// let solicitation = Icmpv6Message::NeighborSolicitation {
+///     target,
+///     options: NdOptions::new(&options),
+/// };
+///
+/// let bytes: Vec<u8> = solicitation.to_bytes(&source, &destination);
+///
+/// match Icmpv6Message::parse(&bytes).expect("invalid message") {
+///     Icmpv6Message::NeighborSolicitation { target: parsed, mut options } => {
+///         assert_eq!(parsed, target);
+///         assert_eq!(options.next(), Some(Ok(NdOption::SourceLinkLayerAddress(mac_addr))))
+///     }
+///     _ => panic!("expected neighbor solicitation"),
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Icmpv6Message<'a> {
+    DestinationUnreachable { code: Icmpv6UnreachableCode, original: &'a [u8] },
+    PacketTooBig { mtu: u32, original: &'a [u8] },
+    TimeExceeded { code: TimeExceededCode, original: &'a [u8] },
+    ParameterProblem { code: u8, pointer: u32, original: &'a [u8] },
+    EchoRequest { id: u16, sequence: u16, data: &'a [u8] },
+    EchoReply { id: u16, sequence: u16, data: &'a [u8] },
+    RouterSolicitation { options: NdOptions<'a> },
+    RouterAdvertisement {
+        hop_limit: u8,
+        managed: bool,
+        other: bool,
+        router_lifetime: u16,
+        reachable_time: u32,
+        retrans_timer: u32,
+        options: NdOptions<'a>,
+    },
+    NeighborSolicitation { target: Ipv6, options: NdOptions<'a> },
+    NeighborAdvertisement {
+        router: bool,
+        solicited: bool,
+        override_: bool,
+        target: Ipv6,
+        options: NdOptions<'a>,
+    },
+    Redirect { target: Ipv6, destination: Ipv6, options: NdOptions<'a> },
+    /// message of other type, `data` follows the checksum
+    Other { type_: u8, code: u8, data: &'a [u8] },
+}
+
+/// code of icmpv6 destination unreachable message
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Icmpv6UnreachableCode {
+    NoRoute,
+    AdministrativelyProhibited,
+    BeyondScope,
+    Address,
+    Port,
+    SourcePolicyFailed,
+    RejectRoute,
+    Other(u8),
+}
+
+/// neighbor discovery option
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NdOption<'a> {
+    SourceLinkLayerAddress(Mac),
+    TargetLinkLayerAddress(Mac),
+    PrefixInformation {
+        prefix_len: u8,
+        on_link: bool,
+        autonomous: bool,
+        valid_lifetime: u32,
+        preferred_lifetime: u32,
+        prefix: Ipv6,
+    },
+    /// part of the packet which caused redirect
+    RedirectedHeader(&'a [u8]),
+    Mtu(u32),
+    /// recursive dns servers (RFC 8106)
+    RecursiveDnsServers { lifetime: u32, servers: Vec<Ipv6> },
+    Unknown { kind: u8, data: &'a [u8] },
+}
+
+/// iterator over neighbor discovery options
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NdOptions<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Icmpv6Message<'a> {
+    /// parses message from bytes starting with icmpv6 header, checksum is not verified
+    pub fn parse(bytes: &'a [u8]) -> Result<Self, ParseError> {
+        let needed: usize = match bytes.first() {
+            Some(1..=4) | Some(128) | Some(129) | Some(133) => 8,
+            Some(134) => 16,
+            Some(135) | Some(136) => 24,
+            Some(137) => 40,
+            _ => ICMPV6_HEADER_SIZE,
+        };
+
+        if bytes.len() < needed {
+            return Err(ParseError::Truncated {
+                needed,
+                available: bytes.len(),
+            });
+        }
+
+        let (type_, code): (u8, u8) = (bytes[0], bytes[1]);
+        let rest: &'a [u8] = &bytes[needed..];
+
+        Ok(match type_ {
+            1 => Self::DestinationUnreachable {
+                code: Handle::from(code),
+                original: rest,
+            },
+            2 => Self::PacketTooBig {
+                mtu: u32_at(bytes, 4),
+                original: rest,
+            },
+            3 => Self::TimeExceeded {
+                code: Handle::from(code),
+                original: rest,
+            },
+            4 => Self::ParameterProblem {
+                code,
+                pointer: u32_at(bytes, 4),
+                original: rest,
+            },
+            128 | 129 => {
+                let id: u16 = u16::from_be_bytes([bytes[4], bytes[5]]);
+                let sequence: u16 = u16::from_be_bytes([bytes[6], bytes[7]]);
+
+                match type_ {
+                    128 => Self::EchoRequest { id, sequence, data: rest },
+                    _ => Self::EchoReply { id, sequence, data: rest },
+                }
+            }
+            133 => Self::RouterSolicitation {
+                options: NdOptions::new(rest),
+            },
+            134 => Self::RouterAdvertisement {
+                hop_limit: bytes[4],
+                managed: bytes[5] & 0x80 != 0,
+                other: bytes[5] & 0x40 != 0,
+                router_lifetime: u16::from_be_bytes([bytes[6], bytes[7]]),
+                reachable_time: u32_at(bytes, 8),
+                retrans_timer: u32_at(bytes, 12),
+                options: NdOptions::new(rest),
+            },
+            135 => Self::NeighborSolicitation {
+                target: ipv6_at(bytes, 8),
+                options: NdOptions::new(rest),
+            },
+            136 => Self::NeighborAdvertisement {
+                router: bytes[4] & 0x80 != 0,
+                solicited: bytes[4] & 0x40 != 0,
+                override_: bytes[4] & 0x20 != 0,
+                target: ipv6_at(bytes, 8),
+                options: NdOptions::new(rest),
+            },
+            137 => Self::Redirect {
+                target: ipv6_at(bytes, 8),
+                destination: ipv6_at(bytes, 24),
+                options: NdOptions::new(rest),
+            },
+            type_ => Self::Other { type_, code, data: rest },
+        })
+    }
+    /// checks checksum of the message carried in the packet with this ipv6 header
+    pub fn verify_checksum(ip_header: &Ipv6Header, bytes: &[u8]) -> bool {
+        bytes.len() >= ICMPV6_HEADER_SIZE
+            && Checksum::new()
+                .add_ipv6_pseudo_header(
+                    &ip_header.source(),
+                    &ip_header.destination(),
+                    IpProtocol::Icmpv6,
+                    bytes.len() as u32,
+                )
+                .add_bytes(bytes)
+                .finish()
+                == 0
+    }
+    pub fn type_(&self) -> u8 {
+        match self {
+            Self::DestinationUnreachable { .. } => 1,
+            Self::PacketTooBig { .. } => 2,
+            Self::TimeExceeded { .. } => 3,
+            Self::ParameterProblem { .. } => 4,
+            Self::EchoRequest { .. } => 128,
+            Self::EchoReply { .. } => 129,
+            Self::RouterSolicitation { .. } => 133,
+            Self::RouterAdvertisement { .. } => 134,
+            Self::NeighborSolicitation { .. } => 135,
+            Self::NeighborAdvertisement { .. } => 136,
+            Self::Redirect { .. } => 137,
+            Self::Other { type_, .. } => *type_,
+        }
+    }
+    pub fn code(&self) -> u8 {
+        match self {
+            Self::DestinationUnreachable { code, .. } => code.to(),
+            Self::TimeExceeded { code, .. } => code.to(),
+            Self::ParameterProblem { code, .. } | Self::Other { code, .. } => *code,
+            _ => 0,
+        }
+    }
+    /// length of encoded message
+    pub fn encoded_len(&self) -> usize {
+        let (body, data): (Body, &[u8]) = self.body();
+
+        ICMPV6_HEADER_SIZE + body.len + data.len()
+    }
+    /// writes message into the buffer with checksum over ipv6 pseudo header and returns its length
+    pub fn write(&self, source: &Ipv6, destination: &Ipv6, buffer: &mut [u8]) -> Result<usize, BuildError> {
+        let (body, data): (Body, &[u8]) = self.body();
+        let body: &[u8] = body.as_slice();
+        let len: usize = ICMPV6_HEADER_SIZE + body.len() + data.len();
+
+        if buffer.len() < len {
+            return Err(BuildError::BufferTooSmall {
+                needed: len,
+                available: buffer.len(),
+            });
+        }
+
+        let buffer: &mut [u8] = &mut buffer[..len];
+        buffer[..ICMPV6_HEADER_SIZE].copy_from_slice(&[self.type_(), self.code(), 0, 0]);
+        buffer[ICMPV6_HEADER_SIZE..ICMPV6_HEADER_SIZE + body.len()].copy_from_slice(body);
+        buffer[ICMPV6_HEADER_SIZE + body.len()..].copy_from_slice(data);
+
+        let check: u16 = Checksum::new()
+            .add_ipv6_pseudo_header(source, destination, IpProtocol::Icmpv6, len as u32)
+            .add_bytes(buffer)
+            .finish();
+        buffer[2..4].copy_from_slice(&check.to_be_bytes());
+
+        Ok(len)
+    }
+    pub fn to_bytes(&self, source: &Ipv6, destination: &Ipv6) -> Vec<u8> {
+        let mut bytes: Vec<u8> = vec![0; self.encoded_len()];
+        match self.write(source, destination, &mut bytes) {
+            Ok(_) => bytes,
+            Err(_) => unreachable!("buffer has the message length"),
+        }
+    }
+    /// fixed part of the message after checksum and trailing variable data
+    fn body(&self) -> (Body, &'a [u8]) {
+        let mut body: Body = Body::new();
+
+        let data: &'a [u8] = match *self {
+            Self::DestinationUnreachable { original, .. } | Self::TimeExceeded { original, .. } => {
+                body.push(&[0; 4]);
+                original
+            }
+            Self::PacketTooBig { mtu: value, original } | Self::ParameterProblem { pointer: value, original, .. } => {
+                body.push(&value.to_be_bytes());
+                original
+            }
+            Self::EchoRequest { id, sequence, data } | Self::EchoReply { id, sequence, data } => {
+                body.push(&id.to_be_bytes());
+                body.push(&sequence.to_be_bytes());
+                data
+            }
+            Self::RouterSolicitation { options } => {
+                body.push(&[0; 4]);
+                options.bytes
+            }
+            Self::RouterAdvertisement {
+                hop_limit,
+                managed,
+                other,
+                router_lifetime,
+                reachable_time,
+                retrans_timer,
+                options,
+            } => {
+                body.push(&[hop_limit]);
+                body.push(&[((managed as u8) << 7) | ((other as u8) << 6)]);
+                body.push(&router_lifetime.to_be_bytes());
+                body.push(&reachable_time.to_be_bytes());
+                body.push(&retrans_timer.to_be_bytes());
+                options.bytes
+            }
+            Self::NeighborSolicitation { target, options } => {
+                body.push(&[0; 4]);
+                body.push(&Handle::<[u8; IPV6_LEN]>::to(&target));
+                options.bytes
+            }
+            Self::NeighborAdvertisement {
+                router,
+                solicited,
+                override_,
+                target,
+                options,
+            } => {
+                body.push(&[((router as u8) << 7) | ((solicited as u8) << 6) | ((override_ as u8) << 5), 0, 0, 0]);
+                body.push(&Handle::<[u8; IPV6_LEN]>::to(&target));
+                options.bytes
+            }
+            Self::Redirect {
+                target,
+                destination,
+                options,
+            } => {
+                body.push(&[0; 4]);
+                body.push(&Handle::<[u8; IPV6_LEN]>::to(&target));
+                body.push(&Handle::<[u8; IPV6_LEN]>::to(&destination));
+                options.bytes
+            }
+            Self::Other { data, .. } => data,
+        };
+
+        (body, data)
+    }
+}
+
+/// fixed part of the message, kept on stack so measuring message doesn't allocate
+struct Body {
+    bytes: [u8; MAX_BODY_SIZE],
+    len: usize,
+}
+
+impl Body {
+    fn new() -> Self {
+        Self {
+            bytes: [0; MAX_BODY_SIZE],
+            len: 0,
+        }
+    }
+    fn push(&mut self, bytes: &[u8]) {
+        self.bytes[self.len..self.len + bytes.len()].copy_from_slice(bytes);
+        self.len += bytes.len();
+    }
+    fn as_slice(&self) -> &[u8] {
+        &self.bytes[..self.len]
+    }
+}
+
+fn ipv6_at(bytes: &[u8], offset: usize) -> Ipv6 {
+    let mut octets: [u8; IPV6_LEN] = [0; IPV6_LEN];
+    octets.copy_from_slice(&bytes[offset..offset + IPV6_LEN]);
+
+    Handle::from(octets)
+}
+
+fn mac_at(bytes: &[u8]) -> Mac {
+    let mut mac_addr: [u8; MAC_LEN] = [0; MAC_LEN];
+    mac_addr.copy_from_slice(&bytes[..MAC_LEN]);
+
+    Handle::from(mac_addr)
+}
+
+impl NdOption<'_> {
+    pub fn kind(&self) -> u8 {
+        match self {
+            Self::SourceLinkLayerAddress(_) => 1,
+            Self::TargetLinkLayerAddress(_) => 2,
+            Self::PrefixInformation { .. } => 3,
+            Self::RedirectedHeader(_) => 4,
+            Self::Mtu(_) => 5,
+            Self::RecursiveDnsServers { .. } => 25,
+            Self::Unknown { kind, .. } => *kind,
+        }
+    }
+    /// encodes option with type and length, padded to 8-byte boundary. option can't be longer than
+    /// 2040 bytes, redirected header is truncated to fit (RFC 4861 section 4.6.3), other options fail
+    ///
+    /// # Example
+    /// ```
+    /// use curuam::*;
+    ///
+    /// let packet: Vec<u8> = vec![0; 4000];
+    /// let servers: Vec<Ipv6> = vec!["2001:db8::53".parse().expect("invalid ip"); 128];
+    ///
+    /// assert_eq!(NdOption::Mtu(1500).to_bytes(), Ok(vec![5, 1, 0, 0, 0, 0, 0x05, 0xdc]));
+    /// assert_eq!(NdOption::RedirectedHeader(&packet).to_bytes().map(|bytes| bytes.len()), Ok(2040));
+    /// assert_eq!(
+    ///     NdOption::RecursiveDnsServers { lifetime: 60, servers }.to_bytes(),
+    ///     Err(BuildError::OptionsTooLong(2056))
+    /// )
+    /// ```
+    pub fn to_bytes(&self) -> Result<Vec<u8>, BuildError> {
+        let mut bytes: Vec<u8> = vec![self.kind(), 0];
+
+        match self {
+            Self::SourceLinkLayerAddress(mac_addr) | Self::TargetLinkLayerAddress(mac_addr) => {
+                bytes.extend_from_slice(&Handle::<[u8; MAC_LEN]>::to(mac_addr))
+            }
+            Self::PrefixInformation {
+                prefix_len,
+                on_link,
+                autonomous,
+                valid_lifetime,
+                preferred_lifetime,
+                prefix,
+            } => {
+                bytes.push(*prefix_len);
+                bytes.push(((*on_link as u8) << 7) | ((*autonomous as u8) << 6));
+                bytes.extend_from_slice(&valid_lifetime.to_be_bytes());
+                bytes.extend_from_slice(&preferred_lifetime.to_be_bytes());
+                bytes.extend_from_slice(&[0; 4]);
+                bytes.extend_from_slice(&Handle::<[u8; IPV6_LEN]>::to(prefix));
+            }
+            Self::RedirectedHeader(header) => {
+                bytes.extend_from_slice(&[0; 6]);
+                bytes.extend_from_slice(&header[..header.len().min(MAX_ND_OPTION_SIZE - 8)]);
+            }
+            Self::Mtu(mtu) => {
+                bytes.extend_from_slice(&[0; 2]);
+                bytes.extend_from_slice(&mtu.to_be_bytes());
+            }
+            Self::RecursiveDnsServers { lifetime, servers } => {
+                bytes.extend_from_slice(&[0; 2]);
+                bytes.extend_from_slice(&lifetime.to_be_bytes());
+                for server in servers {
+                    bytes.extend_from_slice(&Handle::<[u8; IPV6_LEN]>::to(server));
+                }
+            }
+            Self::Unknown { data, .. } => bytes.extend_from_slice(data),
+        }
+
+        bytes.resize(bytes.len().div_ceil(8) * 8, 0);
+        if bytes.len() > MAX_ND_OPTION_SIZE {
+            return Err(BuildError::OptionsTooLong(bytes.len()));
+        }
+        bytes[1] = (bytes.len() / 8) as u8;
+
+        Ok(bytes)
+    }
+    /// encodes options one after another
+    pub fn encode(options: &[NdOption]) -> Result<Vec<u8>, BuildError> {
+        let mut bytes: Vec<u8> = Vec::new();
+        for option in options {
+            bytes.extend_from_slice(&option.to_bytes()?);
+        }
+
+        Ok(bytes)
+    }
+}
+
+impl<'a> NdOptions<'a> {
+    /// creates iterator over raw options bytes
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+}
+
+impl<'a> Iterator for NdOptions<'a> {
+    type Item = Result<NdOption<'a>, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let kind: u8 = *self.bytes.first()?;
+
+        // zero length is invalid and would loop forever
+        let len: usize = match self.bytes.get(1) {
+            Some(&len) if len != 0 && 8 * len as usize <= self.bytes.len() => 8 * len as usize,
+            _ => {
+                self.bytes = &[];
+                return Some(Err(ParseError::InvalidOption(kind)));
+            }
+        };
+
+        let option: &'a [u8] = &self.bytes[..len];
+        let data: &'a [u8] = &option[2..];
+        self.bytes = &self.bytes[len..];
+
+        Some(Ok(match kind {
+            1 if len == 8 => NdOption::SourceLinkLayerAddress(mac_at(data)),
+            2 if len == 8 => NdOption::TargetLinkLayerAddress(mac_at(data)),
+            3 if len == 32 => NdOption::PrefixInformation {
+                prefix_len: data[0],
+                on_link: data[1] & 0x80 != 0,
+                autonomous: data[1] & 0x40 != 0,
+                valid_lifetime: u32_at(data, 2),
+                preferred_lifetime: u32_at(data, 6),
+                prefix: ipv6_at(data, 14),
+            },
+            4 => NdOption::RedirectedHeader(&data[6..]),
+            5 if len == 8 => NdOption::Mtu(u32_at(data, 2)),
+            25 if len >= 24 && (len - 8) % IPV6_LEN == 0 => NdOption::RecursiveDnsServers {
+                lifetime: u32_at(data, 2),
+                servers: data[6..].chunks_exact(IPV6_LEN).map(|server| ipv6_at(server, 0)).collect(),
+            },
+            3 | 5 | 25 => return Some(Err(ParseError::InvalidOption(kind))),
+            kind => NdOption::Unknown { kind, data },
+        }))
+    }
+}
+
+impl Handle<u8> for Icmpv6UnreachableCode {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => Self::NoRoute,
+            1 => Self::AdministrativelyProhibited,
+            2 => Self::BeyondScope,
+            3 => Self::Address,
+            4 => Self::Port,
+            5 => Self::SourcePolicyFailed,
+            6 => Self::RejectRoute,
+            value => Self::Other(value),
+        }
+    }
+    fn to(&self) -> u8 {
+        match *self {
+            Self::NoRoute => 0,
+            Self::AdministrativelyProhibited => 1,
+            Self::BeyondScope => 2,
+            Self::Address => 3,
+            Self::Port => 4,
+            Self::SourcePolicyFailed => 5,
+            Self::RejectRoute => 6,
+            Self::Other(value) => value,
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use crate::Handle;
+
+/// protocol of ipv4 payload or ipv6 next header
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IpProtocol {
+    /// ipv6 hop-by-hop options
+    HopByHop,
+    Icmp,
+    Igmp,
+    Tcp,
+    Udp,
+    /// ipv6 encapsulation
+    Ipv6,
+    /// ipv6 routing header
+    Ipv6Route,
+    /// ipv6 fragment header
+    Ipv6Fragment,
+    Gre,
+    Esp,
+    Ah,
+    Icmpv6,
+    /// ipv6 no next header
+    Ipv6NoNext,
+    /// ipv6 destination options
+    Ipv6Options,
+    Sctp,
+    Other(u8),
+}
+
+impl Handle<u8> for IpProtocol {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => Self::HopByHop,
+            1 => Self::Icmp,
+            2 => Self::Igmp,
+            6 => Self::Tcp,
+            17 => Self::Udp,
+            41 => Self::Ipv6,
+            43 => Self::Ipv6Route,
+            44 => Self::Ipv6Fragment,
+            47 => Self::Gre,
+            50 => Self::Esp,
+            51 => Self::Ah,
+            58 => Self::Icmpv6,
+            59 => Self::Ipv6NoNext,
+            60 => Self::Ipv6Options,
+            132 => Self::Sctp,
+            value => Self::Other(value),
+        }
+    }
+    fn to(&self) -> u8 {
+        match *self {
+            Self::HopByHop => 0,
+            Self::Icmp => 1,
+            Self::Igmp => 2,
+            Self::Tcp => 6,
+            Self::Udp => 17,
+            Self::Ipv6 => 41,
+            Self::Ipv6Route => 43,
+            Self::Ipv6Fragment => 44,
+            Self::Gre => 47,
+            Self::Esp => 50,
+            Self::Ah => 51,
+            Self::Icmpv6 => 58,
+            Self::Ipv6NoNext => 59,
+            Self::Ipv6Options => 60,
+            Self::Sctp => 132,
+            Self::Other(value) => value,
+        }
+    }
+}
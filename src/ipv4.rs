@@ -0,0 +1,607 @@
+use crate::{
+    BuildError, Checksum, FromBytes, Handle, IpProtocol, Ipv4, Ipv4Header, ParseError, U16Be, IPV4_LEN,
+    IP_HEADER_SIZE,
+};
+
+const DONT_FRAGMENT: u16 = 0x4000;
+const MORE_FRAGMENTS: u16 = 0x2000;
+const FRAGMENT_OFFSET: u16 = 0x1fff;
+const MAX_OPTIONS_LEN: usize = 40;
+const MAX_DSCP: u8 = 0x3f;
+const MAX_ECN: u8 = 0x03;
+const DEFAULT_TTL: u8 = 64;
+
+/// builder of ipv4 packets which fills total length, ihl and header checksum
+///
+/// # Example
+/// ```
+/// use curuam::*;
+///
+/// let source: Ipv4 = "10.0.0.1".parse().expect("invalid ip");
+/// let destination: Ipv4 = "10.0.0.2".parse().expect("invalid ip");
+///
+/// let packet: Vec<u8> = Ipv4Header::builder(source, destination, IpProtocol::Udp)
+///     .ttl(32)
+///     .id(0x1234)
+///     .dscp(46)
+///     .expect("invalid dscp")
+///     .payload(&[0xde, 0xad])
+///     .build()
+///     .expect("packet is too large");
+///
+/// let (header, payload) = Ipv4Header::parse(&packet).expect("invalid packet");
+///
+/// assert_eq!(header.tot_len.get(), 22);
+/// assert_eq!(header.ihl(), 5);
+/// assert_eq!(header.ttl, 32);
+/// assert_eq!(header.dscp(), 46);
+/// assert_eq!(header.source(), source);
+/// assert_eq!(Checksum::compute(&packet[..IP_HEADER_SIZE]), 0);
+/// assert_eq!(payload, [0xde, 0xad])
+/// ```
+#[derive(Debug, Clone)]
+pub struct Ipv4HeaderBuilder<'a> {
+    source: Ipv4,
+    destination: Ipv4,
+    protocol: IpProtocol,
+    ttl: u8,
+    dscp: u8,
+    ecn: u8,
+    id: u16,
+    dont_fragment: bool,
+    options: Vec<u8>,
+    payload: &'a [u8],
+}
+
+impl Ipv4Header {
+    pub fn builder<'a>(source: Ipv4, destination: Ipv4, protocol: IpProtocol) -> Ipv4HeaderBuilder<'a> {
+        Ipv4HeaderBuilder::new(source, destination, protocol)
+    }
+    /// splits packet into validated header and payload, payload is trimmed to the total length
+    /// and does not include options
+    ///
+    /// # Example
+    /// ```
+    /// use curuam::*;
+    ///
+    /// let packet: [u8; 22] = [
+    ///     0x45, 0x00, 0x00, 0x16, 0x12, 0x34, 0x40, 0x00, 0x40, 0x11, 0x00, 0x00,
+    ///     10, 0, 0, 1, 10, 0, 0, 2, 0xde, 0xad,
+    /// ];
+    ///
+    /// let (header, payload) = Ipv4Header::parse(&packet).expect("invalid packet");
+    ///
+    /// assert_eq!(header.version(), 4);
+    /// assert_eq!(header.header_len_bytes(), 20);
+    /// assert!(header.dont_fragment());
+    /// assert_eq!(header.protocol(), IpProtocol::Udp);
+    /// assert_eq!(payload, [0xde, 0xad])
+    /// ```
+    pub fn parse(packet: &[u8]) -> Result<(&Ipv4Header, &[u8]), ParseError> {
+        let (header, _): (&Ipv4Header, &[u8]) = Ipv4Header::ref_from(packet)?;
+        header.validate()?;
+
+        let header_len: usize = header.header_len_bytes();
+        if header_len > packet.len() {
+            return Err(ParseError::Truncated {
+                needed: header_len,
+                available: packet.len(),
+            });
+        }
+
+        let total_len: usize = header.tot_len.get() as usize;
+        if total_len < header_len || total_len > packet.len() {
+            return Err(ParseError::InvalidTotalLength(total_len));
+        }
+
+        Ok((header, &packet[header_len..total_len]))
+    }
+    /// checks that version is 4 and header length is at least 20 bytes
+    pub fn validate(&self) -> Result<(), ParseError> {
+        if self.version() != 4 {
+            return Err(ParseError::InvalidVersion(self.version()));
+        }
+        if self.header_len_bytes() < IP_HEADER_SIZE {
+            return Err(ParseError::InvalidHeaderLength(self.header_len_bytes()));
+        }
+
+        Ok(())
+    }
+    pub fn version(&self) -> u8 {
+        self.verihl >> 4
+    }
+    /// `None` if version doesn't fit into 4 bits, header is left unchanged then
+    pub fn set_version(&mut self, version: u8) -> Option<()> {
+        if version > 0x0f {
+            return None;
+        }
+
+        self.verihl = (version << 4) | (self.verihl & 0x0f);
+        Some(())
+    }
+    /// header length in 32-bit words
+    pub fn ihl(&self) -> u8 {
+        self.verihl & 0x0f
+    }
+    /// `None` if ihl doesn't fit into 4 bits, header is left unchanged then
+    pub fn set_ihl(&mut self, ihl: u8) -> Option<()> {
+        if ihl > 0x0f {
+            return None;
+        }
+
+        self.verihl = (self.verihl & 0xf0) | ihl;
+        Some(())
+    }
+    pub fn header_len_bytes(&self) -> usize {
+        4 * self.ihl() as usize
+    }
+    /// differentiated services code point, upper 6 bits of type of service
+    pub fn dscp(&self) -> u8 {
+        self.tos >> 2
+    }
+    /// `None` if dscp doesn't fit into 6 bits, header is left unchanged then
+    ///
+    /// # Example
+    /// ```
+    /// use curuam::*;
+    ///
+    /// let source: Ipv4 = "10.0.0.1".parse().expect("invalid ip");
+    /// let destination: Ipv4 = "10.0.0.2".parse().expect("invalid ip");
+    ///
+    /// let mut header: Ipv4Header = Ipv4Header::builder(source, destination, IpProtocol::Udp)
+    ///     .header()
+    ///     .expect("invalid header");
+    ///
+    /// assert_eq!(header.set_dscp(46), Some(()));
+    /// assert_eq!(header.set_dscp(64), None);
+    /// assert_eq!(header.set_ecn(4), None);
+    /// assert_eq!((header.dscp(), header.ecn()), (46, 0))
+    /// ```
+    pub fn set_dscp(&mut self, dscp: u8) -> Option<()> {
+        if dscp > MAX_DSCP {
+            return None;
+        }
+
+        self.tos = (dscp << 2) | (self.tos & 0x03);
+        Some(())
+    }
+    /// explicit congestion notification, lower 2 bits of type of service
+    pub fn ecn(&self) -> u8 {
+        self.tos & 0x03
+    }
+    /// `None` if ecn doesn't fit into 2 bits, header is left unchanged then
+    pub fn set_ecn(&mut self, ecn: u8) -> Option<()> {
+        if ecn > MAX_ECN {
+            return None;
+        }
+
+        self.tos = (self.tos & 0xfc) | ecn;
+        Some(())
+    }
+    pub fn dont_fragment(&self) -> bool {
+        self.frag.get() & DONT_FRAGMENT != 0
+    }
+    pub fn set_dont_fragment(&mut self, dont_fragment: bool) {
+        self.set_frag_flag(DONT_FRAGMENT, dont_fragment)
+    }
+    pub fn more_fragments(&self) -> bool {
+        self.frag.get() & MORE_FRAGMENTS != 0
+    }
+    pub fn set_more_fragments(&mut self, more_fragments: bool) {
+        self.set_frag_flag(MORE_FRAGMENTS, more_fragments)
+    }
+    /// fragment offset in 8-byte units
+    pub fn fragment_offset(&self) -> u16 {
+        self.frag.get() & FRAGMENT_OFFSET
+    }
+    /// `None` if offset doesn't fit into 13 bits, header is left unchanged then
+    pub fn set_fragment_offset(&mut self, offset: u16) -> Option<()> {
+        if offset > FRAGMENT_OFFSET {
+            return None;
+        }
+
+        self.frag.set((self.frag.get() & !FRAGMENT_OFFSET) | offset);
+        Some(())
+    }
+    pub fn protocol(&self) -> IpProtocol {
+        Handle::from(self.protocol)
+    }
+    pub fn set_protocol(&mut self, protocol: IpProtocol) {
+        self.protocol = protocol.to()
+    }
+    pub fn source(&self) -> Ipv4 {
+        Handle::from(self.saddr)
+    }
+    pub fn set_source(&mut self, ip_addr: Ipv4) {
+        self.saddr = ip_addr.to()
+    }
+    pub fn destination(&self) -> Ipv4 {
+        Handle::from(self.daddr)
+    }
+    pub fn set_destination(&mut self, ip_addr: Ipv4) {
+        self.daddr = ip_addr.to()
+    }
+    fn set_frag_flag(&mut self, flag: u16, value: bool) {
+        match value {
+            true => self.frag.set(self.frag.get() | flag),
+            false => self.frag.set(self.frag.get() & !flag),
+        }
+    }
+}
+
+impl<'a> Ipv4HeaderBuilder<'a> {
+    pub fn new(source: Ipv4, destination: Ipv4, protocol: IpProtocol) -> Self {
+        Self {
+            source,
+            destination,
+            protocol,
+            ttl: DEFAULT_TTL,
+            dscp: 0,
+            ecn: 0,
+            id: 0,
+            dont_fragment: false,
+            options: Vec::new(),
+            payload: &[],
+        }
+    }
+    pub fn ttl(mut self, ttl: u8) -> Self {
+        self.ttl = ttl;
+        self
+    }
+    /// `None` if dscp doesn't fit into 6 bits
+    pub fn dscp(mut self, dscp: u8) -> Option<Self> {
+        if dscp > MAX_DSCP {
+            return None;
+        }
+
+        self.dscp = dscp;
+        Some(self)
+    }
+    /// `None` if ecn doesn't fit into 2 bits
+    pub fn ecn(mut self, ecn: u8) -> Option<Self> {
+        if ecn > MAX_ECN {
+            return None;
+        }
+
+        self.ecn = ecn;
+        Some(self)
+    }
+    pub fn id(mut self, id: u16) -> Self {
+        self.id = id;
+        self
+    }
+    pub fn dont_fragment(mut self, dont_fragment: bool) -> Self {
+        self.dont_fragment = dont_fragment;
+        self
+    }
+    /// appends already encoded options, they are padded to 32-bit boundary when written
+    pub fn raw_options(mut self, options: &[u8]) -> Self {
+        self.options.extend_from_slice(options);
+        self
+    }
+    pub fn payload(mut self, payload: &'a [u8]) -> Self {
+        self.payload = payload;
+        self
+    }
+    /// header length including padded options
+    pub fn header_len(&self) -> usize {
+        IP_HEADER_SIZE + self.options.len().div_ceil(4) * 4
+    }
+    pub fn total_len(&self) -> usize {
+        self.header_len() + self.payload.len()
+    }
+    /// creates fixed part of the header, checksum covers the options too
+    pub fn header(&self) -> Result<Ipv4Header, BuildError> {
+        if self.options.len() > MAX_OPTIONS_LEN {
+            return Err(BuildError::OptionsTooLong(self.options.len()));
+        }
+        if self.total_len() > u16::MAX as usize {
+            return Err(BuildError::PayloadTooLarge(self.payload.len()));
+        }
+
+        // version is 4 and ihl is at most 15 as options are checked above
+        let mut header: Ipv4Header = Ipv4Header {
+            verihl: 0x40 | (self.header_len() / 4) as u8,
+            tos: (self.dscp << 2) | self.ecn,
+            tot_len: U16Be::new(self.total_len() as u16),
+            id: U16Be::new(self.id),
+            frag: U16Be::new(0),
+            ttl: self.ttl,
+            protocol: self.protocol.to(),
+            check: U16Be::new(0),
+            saddr: self.source.to(),
+            daddr: self.destination.to(),
+        };
+        header.set_dont_fragment(self.dont_fragment);
+
+        let padding: [u8; 3] = [0; 3];
+        let check: u16 = Checksum::new()
+            .add_bytes(header.as_bytes())
+            .add_bytes(&self.options)
+            .add_bytes(&padding[..self.header_len() - IP_HEADER_SIZE - self.options.len()])
+            .finish();
+        header.check.set(check);
+
+        Ok(header)
+    }
+    /// writes whole packet into the buffer and returns its length
+    pub fn write(&self, buffer: &mut [u8]) -> Result<usize, BuildError> {
+        let header: Ipv4Header = self.header()?;
+        let header_len: usize = self.header_len();
+        let total_len: usize = self.total_len();
+
+        if buffer.len() < total_len {
+            return Err(BuildError::BufferTooSmall {
+                needed: total_len,
+                available: buffer.len(),
+            });
+        }
+
+        buffer[..IP_HEADER_SIZE].copy_from_slice(header.as_bytes());
+        buffer[IP_HEADER_SIZE..IP_HEADER_SIZE + self.options.len()].copy_from_slice(&self.options);
+        buffer[IP_HEADER_SIZE + self.options.len()..header_len].fill(0);
+        buffer[header_len..total_len].copy_from_slice(self.payload);
+
+        Ok(total_len)
+    }
+    pub fn build(&self) -> Result<Vec<u8>, BuildError> {
+        let mut packet: Vec<u8> = vec![0; self.total_len()];
+        self.write(&mut packet)?;
+
+        Ok(packet)
+    }
+}
+
+/// ipv4 header option
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Ipv4Option {
+    EndOfList,
+    NoOperation,
+    RecordRoute { pointer: u8, route: Vec<Ipv4> },
+    /// internet timestamp (RFC 791), address is present for flags 1 and 3
+    Timestamp {
+        pointer: u8,
+        overflow: u8,
+        flag: u8,
+        entries: Vec<(Option<Ipv4>, u32)>,
+    },
+    LooseSourceRoute { pointer: u8, route: Vec<Ipv4> },
+    StrictSourceRoute { pointer: u8, route: Vec<Ipv4> },
+    /// router alert (RFC 2113)
+    RouterAlert(u16),
+    /// basic security option (RFC 1108)
+    Security { classification: u8, authority: Vec<u8> },
+    Unknown { kind: u8, data: Vec<u8> },
+}
+
+/// iterator over options of ipv4 header, stops after end of list option
+///
+/// # Example
+/// ```
+/// use curuam::*;
+///
+/// let source: Ipv4 = "10.0.0.1".parse().expect("invalid ip");
+/// let destination: Ipv4 = "10.0.0.2".parse().expect("invalid ip");
+///
+/// let packet: Vec<u8> = Ipv4Header::builder(source, destination, IpProtocol::Igmp)
+///     .option(Ipv4Option::RouterAlert(0))
+///     .option(Ipv4Option::NoOperation)
+///     .build()
+///     .expect("invalid options");
+///
+/// let (header, options, _) = Ipv4Header::parse_with_options(&packet).expect("invalid packet");
+/// let options: Vec<Ipv4Option> = options.collect::<Result<_, _>>().expect("invalid option");
+///
+/// assert_eq!(header.ihl(), 7);
+/// assert_eq!(options, [Ipv4Option::RouterAlert(0), Ipv4Option::NoOperation, Ipv4Option::EndOfList])
+/// ```
+#[derive(Debug, Clone)]
+pub struct Ipv4Options<'a> {
+    bytes: &'a [u8],
+}
+
+impl Ipv4Header {
+    /// same as [`Ipv4Header::parse`] but also returns options between fixed header and payload
+    pub fn parse_with_options(packet: &[u8]) -> Result<(&Ipv4Header, Ipv4Options<'_>, &[u8]), ParseError> {
+        let (header, payload): (&Ipv4Header, &[u8]) = Ipv4Header::parse(packet)?;
+        let options: Ipv4Options = Ipv4Options::new(&packet[IP_HEADER_SIZE..header.header_len_bytes()]);
+
+        Ok((header, options, payload))
+    }
+}
+
+impl<'a> Ipv4HeaderBuilder<'a> {
+    /// appends option, options are padded with end of list options to 32-bit boundary when written,
+    /// options longer than 40 bytes are reported by [`Ipv4HeaderBuilder::write`]
+    pub fn option(self, option: Ipv4Option) -> Self {
+        self.raw_options(&option.encode())
+    }
+}
+
+impl Ipv4Option {
+    pub fn kind(&self) -> u8 {
+        match self {
+            Self::EndOfList => 0,
+            Self::NoOperation => 1,
+            Self::RecordRoute { .. } => 7,
+            Self::Timestamp { .. } => 68,
+            Self::LooseSourceRoute { .. } => 131,
+            Self::StrictSourceRoute { .. } => 137,
+            Self::RouterAlert(_) => 148,
+            Self::Security { .. } => 130,
+            Self::Unknown { kind, .. } => *kind,
+        }
+    }
+    /// encodes option with kind and length bytes, fails if it doesn't fit in 40 bytes of options
+    ///
+    /// # Example
+    /// ```
+    /// use curuam::*;
+    ///
+    /// let route: Vec<Ipv4> = vec!["10.0.0.1".parse().expect("invalid ip"); 10];
+    ///
+    /// assert_eq!(Ipv4Option::RouterAlert(0).to_bytes(), Ok(vec![148, 4, 0, 0]));
+    /// assert_eq!(
+    ///     Ipv4Option::RecordRoute { pointer: 4, route }.to_bytes(),
+    ///     Err(BuildError::OptionsTooLong(43))
+    /// )
+    /// ```
+    pub fn to_bytes(&self) -> Result<Vec<u8>, BuildError> {
+        let bytes: Vec<u8> = self.encode();
+        if bytes.len() > MAX_OPTIONS_LEN {
+            return Err(BuildError::OptionsTooLong(bytes.len()));
+        }
+
+        Ok(bytes)
+    }
+    /// encodes option without checking its length, length byte saturates at 255
+    fn encode(&self) -> Vec<u8> {
+        let mut bytes: Vec<u8> = vec![self.kind()];
+
+        match self {
+            Self::EndOfList | Self::NoOperation => return bytes,
+            Self::RecordRoute { pointer, route }
+            | Self::LooseSourceRoute { pointer, route }
+            | Self::StrictSourceRoute { pointer, route } => {
+                bytes.extend_from_slice(&[0, *pointer]);
+                for ip_addr in route {
+                    bytes.extend_from_slice(&Handle::<[u8; IPV4_LEN]>::to(ip_addr));
+                }
+            }
+            Self::Timestamp { pointer, overflow, flag, entries } => {
+                bytes.extend_from_slice(&[0, *pointer, (overflow << 4) | (flag & 0x0f)]);
+                for (ip_addr, timestamp) in entries {
+                    if let Some(ip_addr) = ip_addr {
+                        bytes.extend_from_slice(&Handle::<[u8; IPV4_LEN]>::to(ip_addr));
+                    }
+                    bytes.extend_from_slice(&timestamp.to_be_bytes());
+                }
+            }
+            Self::RouterAlert(value) => {
+                bytes.push(0);
+                bytes.extend_from_slice(&value.to_be_bytes());
+            }
+            Self::Security { classification, authority } => {
+                bytes.extend_from_slice(&[0, *classification]);
+                bytes.extend_from_slice(authority);
+            }
+            Self::Unknown { data, .. } => {
+                bytes.push(0);
+                bytes.extend_from_slice(data);
+            }
+        }
+
+        bytes[1] = u8::try_from(bytes.len()).unwrap_or(u8::MAX);
+
+        bytes
+    }
+    fn parse(kind: u8, data: &[u8]) -> Result<Self, ParseError> {
+        let invalid: ParseError = ParseError::InvalidOption(kind);
+
+        match kind {
+            7 | 131 | 137 => {
+                let (&pointer, route): (&u8, &[u8]) = data.split_first().ok_or(invalid)?;
+                if route.len() % IPV4_LEN != 0 {
+                    return Err(invalid);
+                }
+
+                let route: Vec<Ipv4> = route.chunks_exact(IPV4_LEN).map(ipv4_from_slice).collect();
+
+                Ok(match kind {
+                    7 => Self::RecordRoute { pointer, route },
+                    131 => Self::LooseSourceRoute { pointer, route },
+                    _ => Self::StrictSourceRoute { pointer, route },
+                })
+            }
+            68 => {
+                let [pointer, flags, entries @ ..] = data else {
+                    return Err(invalid);
+                };
+
+                let flag: u8 = flags & 0x0f;
+                let with_address: bool = flag == 1 || flag == 3;
+                let entry_len: usize = if with_address { 8 } else { 4 };
+                if entries.len() % entry_len != 0 {
+                    return Err(invalid);
+                }
+
+                let entries: Vec<(Option<Ipv4>, u32)> = entries
+                    .chunks_exact(entry_len)
+                    .map(|entry| {
+                        let (ip_addr, timestamp): (&[u8], &[u8]) = entry.split_at(entry_len - 4);
+                        let ip_addr: Option<Ipv4> = with_address.then(|| ipv4_from_slice(ip_addr));
+
+                        (ip_addr, u32::from_be_bytes([timestamp[0], timestamp[1], timestamp[2], timestamp[3]]))
+                    })
+                    .collect();
+
+                Ok(Self::Timestamp {
+                    pointer: *pointer,
+                    overflow: flags >> 4,
+                    flag,
+                    entries,
+                })
+            }
+            148 => match data {
+                [high, low] => Ok(Self::RouterAlert(u16::from_be_bytes([*high, *low]))),
+                _ => Err(invalid),
+            },
+            130 => {
+                let (&classification, authority): (&u8, &[u8]) = data.split_first().ok_or(invalid)?;
+
+                Ok(Self::Security {
+                    classification,
+                    authority: authority.to_vec(),
+                })
+            }
+            kind => Ok(Self::Unknown {
+                kind,
+                data: data.to_vec(),
+            }),
+        }
+    }
+}
+
+fn ipv4_from_slice(bytes: &[u8]) -> Ipv4 {
+    Handle::from([bytes[0], bytes[1], bytes[2], bytes[3]])
+}
+
+impl<'a> Ipv4Options<'a> {
+    /// creates iterator over raw options bytes
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+}
+
+impl Iterator for Ipv4Options<'_> {
+    type Item = Result<Ipv4Option, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (&kind, rest): (&u8, &[u8]) = self.bytes.split_first()?;
+
+        match kind {
+            0 => {
+                self.bytes = &[];
+                return Some(Ok(Ipv4Option::EndOfList));
+            }
+            1 => {
+                self.bytes = rest;
+                return Some(Ok(Ipv4Option::NoOperation));
+            }
+            _ => {}
+        }
+
+        let len: usize = match rest.first() {
+            Some(&len) if len >= 2 && len as usize <= self.bytes.len() => len as usize,
+            _ => {
+                self.bytes = &[];
+                return Some(Err(ParseError::InvalidOption(kind)));
+            }
+        };
+
+        let data: &[u8] = &self.bytes[2..len];
+        self.bytes = &self.bytes[len..];
+
+        Some(Ipv4Option::parse(kind, data))
+    }
+}
@@ -0,0 +1,378 @@
+use crate::{u32_at, FromBytes, Handle, IpProtocol, Ipv6, Ipv6Header, ParseError, IPV6_LEN};
+
+const FLOW_LABEL: u32 = 0x000f_ffff;
+
+impl Ipv6Header {
+    /// splits packet into validated header and payload, payload is trimmed to the payload length
+    ///
+    /// # Example
+    /// ```
+    /// use curuam::*;
+    ///
+    /// let mut packet: [u8; 42] = [0; 42];
+    /// packet[..8].copy_from_slice(&[0x6b, 0x81, 0x23, 0x45, 0x00, 0x02, 0x3a, 0x40]);
+    /// packet[8..24].copy_from_slice(&Handle::<[u8; IPV6_LEN]>::to(&"fe80::1".parse::<Ipv6>().expect("invalid ip")));
+    ///
+    /// let (header, payload) = Ipv6Header::parse(&packet).expect("invalid packet");
+    ///
+    /// assert_eq!(header.version(), 6);
+    /// assert_eq!(header.traffic_class(), 0xb8);
+    /// assert_eq!(header.dscp(), 46);
+    /// assert_eq!(header.flow_label(), 0x12345);
+    /// assert_eq!(header.next_header(), IpProtocol::Icmpv6);
+    /// assert_eq!(header.source().to_string(), "fe80::1");
+    /// assert_eq!(payload.len(), 2)
+    /// ```
+    pub fn parse(packet: &[u8]) -> Result<(&Ipv6Header, &[u8]), ParseError> {
+        let (header, rest): (&Ipv6Header, &[u8]) = Ipv6Header::ref_from(packet)?;
+        if header.version() != 6 {
+            return Err(ParseError::InvalidVersion(header.version()));
+        }
+
+        let payload_len: usize = header.payload.get() as usize;
+        if payload_len > rest.len() {
+            return Err(ParseError::InvalidTotalLength(payload_len));
+        }
+
+        // zero payload length with hop-by-hop header means jumbogram (RFC 2675)
+        if payload_len == 0 && header.next_header() == IpProtocol::HopByHop {
+            return Ok((header, rest));
+        }
+
+        Ok((header, &rest[..payload_len]))
+    }
+    pub fn version(&self) -> u8 {
+        (self.verlab.get() >> 28) as u8
+    }
+    pub fn set_version(&mut self, version: u8) {
+        self.verlab.set((self.verlab.get() & 0x0fff_ffff) | ((version as u32 & 0x0f) << 28))
+    }
+    pub fn traffic_class(&self) -> u8 {
+        (self.verlab.get() >> 20) as u8
+    }
+    pub fn set_traffic_class(&mut self, traffic_class: u8) {
+        self.verlab.set((self.verlab.get() & 0xf00f_ffff) | ((traffic_class as u32) << 20))
+    }
+    /// differentiated services code point, upper 6 bits of traffic class
+    pub fn dscp(&self) -> u8 {
+        self.traffic_class() >> 2
+    }
+    pub fn set_dscp(&mut self, dscp: u8) {
+        self.set_traffic_class((dscp << 2) | self.ecn())
+    }
+    /// explicit congestion notification, lower 2 bits of traffic class
+    pub fn ecn(&self) -> u8 {
+        self.traffic_class() & 0x03
+    }
+    pub fn set_ecn(&mut self, ecn: u8) {
+        self.set_traffic_class((self.traffic_class() & 0xfc) | (ecn & 0x03))
+    }
+    /// 20-bit flow label
+    pub fn flow_label(&self) -> u32 {
+        self.verlab.get() & FLOW_LABEL
+    }
+    pub fn set_flow_label(&mut self, flow_label: u32) {
+        self.verlab.set((self.verlab.get() & !FLOW_LABEL) | (flow_label & FLOW_LABEL))
+    }
+    pub fn next_header(&self) -> IpProtocol {
+        Handle::from(self.next)
+    }
+    pub fn set_next_header(&mut self, next_header: IpProtocol) {
+        self.next = next_header.to()
+    }
+    pub fn source(&self) -> Ipv6 {
+        Handle::from(self.src)
+    }
+    pub fn set_source(&mut self, ip_addr: Ipv6) {
+        self.src = ip_addr.to()
+    }
+    pub fn destination(&self) -> Ipv6 {
+        Handle::from(self.dst)
+    }
+    pub fn set_destination(&mut self, ip_addr: Ipv6) {
+        self.dst = ip_addr.to()
+    }
+}
+
+const SEGMENT_ROUTING: u8 = 4;
+
+/// ipv6 extension header borrowed from packet
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Ipv6Extension<'a> {
+    HopByHop(Ipv6Options<'a>),
+    Routing(Ipv6Routing<'a>),
+    Fragment(Ipv6Fragment),
+    DestinationOptions(Ipv6Options<'a>),
+    /// authentication header (RFC 4302)
+    Authentication { spi: u32, sequence: u32, icv: &'a [u8] },
+    /// encapsulating security payload (RFC 4303), everything after it is encrypted
+    Esp { spi: u32, sequence: u32 },
+}
+
+/// routing header, segment list of segment routing header is available through [`Ipv6Routing::segments`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ipv6Routing<'a> {
+    pub routing_type: u8,
+    pub segments_left: u8,
+    /// type specific data following the first 4 bytes
+    pub data: &'a [u8],
+}
+
+/// fragment header
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ipv6Fragment {
+    /// fragment offset in 8-byte units
+    pub offset: u16,
+    pub more_fragments: bool,
+    pub id: u32,
+}
+
+/// iterator over tlv options of hop-by-hop and destination options headers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ipv6Options<'a> {
+    bytes: &'a [u8],
+}
+
+/// option of hop-by-hop or destination options header
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ipv6Option<'a> {
+    Pad1,
+    PadN(usize),
+    /// router alert (RFC 2711)
+    RouterAlert(u16),
+    /// jumbo payload length (RFC 2675)
+    JumboPayload(u32),
+    Unknown { kind: u8, data: &'a [u8] },
+}
+
+/// iterator over extension header chain, stops at the first upper-layer protocol
+///
+/// # Example
+/// ```
+/// use curuam::*;
+///
+/// let payload: [u8; 20] = [
+///     // hop-by-hop: next is fragment, router alert option and padding
+///     44, 0, 5, 2, 0, 0, 1, 0,
+///     // fragment: next is udp, offset 0, more fragments
+///     17, 0, 0x00, 0x01, 0x00, 0x00, 0x00, 0x2a,
+///     // udp
+///     0xde, 0xad, 0xbe, 0xef,
+/// ];
+///
+/// let mut extensions = Ipv6Extensions::new(IpProtocol::HopByHop, &payload);
+///
+/// match extensions.next() {
+///     Some(Ok(Ipv6Extension::HopByHop(mut options))) => {
+///         assert_eq!(options.next(), Some(Ok(Ipv6Option::RouterAlert(0))))
+///     }
+///     _ => panic!("expected hop-by-hop header"),
+/// }
+///
+/// let (protocol, upper_layer) = extensions.upper_layer().expect("invalid extension");
+///
+/// assert_eq!(protocol, IpProtocol::Udp);
+/// assert_eq!(upper_layer, [0xde, 0xad, 0xbe, 0xef])
+/// ```
+#[derive(Debug, Clone)]
+pub struct Ipv6Extensions<'a> {
+    next: IpProtocol,
+    bytes: &'a [u8],
+}
+
+impl Ipv6Header {
+    /// returns iterator over extension headers at the start of the payload
+    pub fn extensions<'a>(&self, payload: &'a [u8]) -> Ipv6Extensions<'a> {
+        Ipv6Extensions::new(self.next_header(), payload)
+    }
+}
+
+impl<'a> Ipv6Extensions<'a> {
+    pub fn new(next: IpProtocol, bytes: &'a [u8]) -> Self {
+        Self { next, bytes }
+    }
+    /// protocol of the header which will be read next
+    pub fn next_header(&self) -> IpProtocol {
+        self.next
+    }
+    /// bytes starting at the header which will be read next
+    pub fn remaining(&self) -> &'a [u8] {
+        self.bytes
+    }
+    /// skips all extension headers and returns upper-layer protocol with its bytes,
+    /// esp is returned as upper-layer protocol because the rest of the packet is encrypted
+    /// and fragment header is returned if it isn't the first fragment
+    ///
+    /// # Example
+    /// ```
+    /// use curuam::*;
+    ///
+    /// let payload: [u8; 12] = [
+    ///     // fragment: next is udp, offset 1, last fragment
+    ///     17, 0, 0x00, 0x08, 0x00, 0x00, 0x00, 0x2a,
+    ///     // middle of udp payload
+    ///     0xde, 0xad, 0xbe, 0xef,
+    /// ];
+    ///
+    /// let (protocol, bytes) = Ipv6Extensions::new(IpProtocol::Ipv6Fragment, &payload)
+    ///     .upper_layer()
+    ///     .expect("invalid extension");
+    ///
+    /// assert_eq!(protocol, IpProtocol::Ipv6Fragment);
+    /// assert_eq!(bytes, payload);
+    ///
+    /// let truncated = Ipv6Extensions::new(IpProtocol::HopByHop, &[0]).upper_layer();
+    ///
+    /// assert_eq!(truncated, Err(ParseError::Truncated { needed: 2, available: 1 }))
+    /// ```
+    pub fn upper_layer(mut self) -> Result<(IpProtocol, &'a [u8]), ParseError> {
+        loop {
+            let (protocol, bytes): (IpProtocol, &[u8]) = (self.next, self.bytes);
+
+            match self.next() {
+                Some(Ok(Ipv6Extension::Esp { .. })) | None => return Ok((protocol, bytes)),
+                Some(Ok(Ipv6Extension::Fragment(fragment))) if fragment.offset != 0 => return Ok((protocol, bytes)),
+                Some(Ok(_)) => {}
+                Some(Err(err)) => return Err(err),
+            }
+        }
+    }
+}
+
+impl<'a> Iterator for Ipv6Extensions<'a> {
+    type Item = Result<Ipv6Extension<'a>, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let len: usize = match (self.next, self.bytes.get(1)) {
+            (IpProtocol::HopByHop | IpProtocol::Ipv6Route | IpProtocol::Ipv6Options, Some(&len)) => {
+                8 * (len as usize + 1)
+            }
+            (IpProtocol::Ah, Some(&len)) => 4 * (len as usize + 2),
+            // length field itself is missing
+            (IpProtocol::HopByHop | IpProtocol::Ipv6Route | IpProtocol::Ipv6Options | IpProtocol::Ah, None) => 2,
+            (IpProtocol::Ipv6Fragment, _) => 8,
+            (IpProtocol::Esp, _) => 8,
+            _ => return None,
+        };
+
+        if self.bytes.len() < len {
+            let available: usize = self.bytes.len();
+            self.bytes = &[];
+            self.next = IpProtocol::Ipv6NoNext;
+
+            return Some(Err(ParseError::Truncated { needed: len, available }));
+        }
+
+        let (header, rest): (&'a [u8], &'a [u8]) = self.bytes.split_at(len);
+
+        let extension: Ipv6Extension = match self.next {
+            IpProtocol::HopByHop => Ipv6Extension::HopByHop(Ipv6Options::new(&header[2..])),
+            IpProtocol::Ipv6Options => Ipv6Extension::DestinationOptions(Ipv6Options::new(&header[2..])),
+            IpProtocol::Ipv6Route => Ipv6Extension::Routing(Ipv6Routing {
+                routing_type: header[2],
+                segments_left: header[3],
+                data: &header[4..],
+            }),
+            IpProtocol::Ipv6Fragment => {
+                let offset: u16 = u16::from_be_bytes([header[2], header[3]]);
+
+                Ipv6Extension::Fragment(Ipv6Fragment {
+                    offset: offset >> 3,
+                    more_fragments: offset & 1 != 0,
+                    id: u32_at(header, 4),
+                })
+            }
+            IpProtocol::Ah => {
+                if len < 12 {
+                    self.bytes = &[];
+                    self.next = IpProtocol::Ipv6NoNext;
+
+                    return Some(Err(ParseError::InvalidHeaderLength(len)));
+                }
+
+                Ipv6Extension::Authentication {
+                    spi: u32_at(header, 4),
+                    sequence: u32_at(header, 8),
+                    icv: &header[12..],
+                }
+            }
+            _ => {
+                // esp hides next header in the encrypted trailer
+                self.bytes = rest;
+                self.next = IpProtocol::Ipv6NoNext;
+
+                return Some(Ok(Ipv6Extension::Esp {
+                    spi: u32_at(header, 0),
+                    sequence: u32_at(header, 4),
+                }));
+            }
+        };
+
+        self.next = Handle::from(header[0]);
+        self.bytes = rest;
+
+        Some(Ok(extension))
+    }
+}
+
+impl<'a> Ipv6Routing<'a> {
+    /// returns segment list of segment routing header (RFC 8754), `None` for other routing types
+    pub fn segments(&self) -> Option<Vec<Ipv6>> {
+        if self.routing_type != SEGMENT_ROUTING {
+            return None;
+        }
+
+        let count: usize = *self.data.first()? as usize + 1;
+        let segments: &[u8] = self.data.get(4..4 + count * IPV6_LEN)?;
+
+        Some(
+            segments
+                .chunks_exact(IPV6_LEN)
+                .map(|segment| {
+                    let mut octets: [u8; IPV6_LEN] = [0; IPV6_LEN];
+                    octets.copy_from_slice(segment);
+
+                    Handle::from(octets)
+                })
+                .collect(),
+        )
+    }
+}
+
+impl<'a> Ipv6Options<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+}
+
+impl<'a> Iterator for Ipv6Options<'a> {
+    type Item = Result<Ipv6Option<'a>, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (&kind, rest): (&u8, &[u8]) = self.bytes.split_first()?;
+
+        if kind == 0 {
+            self.bytes = rest;
+            return Some(Ok(Ipv6Option::Pad1));
+        }
+
+        let len: usize = match rest.first() {
+            Some(&len) if len as usize + 2 <= self.bytes.len() => len as usize + 2,
+            _ => {
+                self.bytes = &[];
+                return Some(Err(ParseError::InvalidOption(kind)));
+            }
+        };
+
+        let data: &[u8] = &self.bytes[2..len];
+        self.bytes = &self.bytes[len..];
+
+        Some(match (kind, data) {
+            (1, data) => Ok(Ipv6Option::PadN(data.len() + 2)),
+            (5, [high, low]) => Ok(Ipv6Option::RouterAlert(u16::from_be_bytes([*high, *low]))),
+            (0xc2, [a, b, c, d]) => Ok(Ipv6Option::JumboPayload(u32::from_be_bytes([*a, *b, *c, *d]))),
+            (5, _) | (0xc2, _) => Err(ParseError::InvalidOption(kind)),
+            (kind, data) => Ok(Ipv6Option::Unknown { kind, data }),
+        })
+    }
+}
@@ -3,13 +3,51 @@ use std::time::{
     SystemTimeError,
 };
 
+mod address;
+mod arp;
+mod checksum;
+mod endian;
+mod eth;
+mod icmp;
+mod icmpv6;
+mod ip;
+mod ipv4;
+mod ipv6;
+mod network;
+mod oui;
+mod pcap;
+#[cfg(feature = "serde")]
+mod serde_impl;
+mod tcp;
+mod udp;
+
+pub use address::*;
+pub use arp::*;
+pub use checksum::*;
+pub use endian::*;
+pub use eth::*;
+pub use icmp::*;
+pub use icmpv6::*;
+pub use ip::*;
+pub use ipv4::*;
+pub use ipv6::*;
+pub use network::*;
+pub use oui::*;
+pub use pcap::*;
+pub use tcp::*;
+pub use udp::*;
+
 pub const ICMP_HEADER_SIZE: usize = std::mem::size_of::<IcmpHeader>();
 pub const ARP_HEADER_SIZE: usize = std::mem::size_of::<ArpHeader>();
 pub const ETH_HEADER_SIZE: usize = std::mem::size_of::<EthHeader>();
 pub const IP_HEADER_SIZE: usize = std::mem::size_of::<Ipv4Header>();
+pub const IPV6_HEADER_SIZE: usize = std::mem::size_of::<Ipv6Header>();
+pub const TCP_HEADER_SIZE: usize = std::mem::size_of::<TcpHeader>();
+pub const UDP_HEADER_SIZE: usize = std::mem::size_of::<UdpHeader>();
 pub const IPV6_LEN: usize = 16;
 pub const IPV4_LEN: usize = 4;
 pub const MAC_LEN: usize = 6;
+pub const EUI64_LEN: usize = 8;
 
 /// trait for conveting one type into other similar to the From trait
 /// # Examples
@@ -60,10 +98,28 @@ pub struct Prime;
 ///
 /// assert_eq!(ip_octets, [192, 168, 1, 1])
 /// ```
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Ipv4 {
     octets: [u8; IPV4_LEN],
 }
 
+/// error returned when parsing ipv4 address from string, octets are counted from zero
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ipv4ParseError {
+    /// input string is empty
+    Empty,
+    /// address has wrong count of octets
+    OctetCount(usize),
+    /// octet with this index is empty
+    EmptyOctet(usize),
+    /// octet contains character which is not a digit
+    InvalidCharacter { octet: usize, character: char },
+    /// octet has leading zero, which is ambiguous with octal notation
+    LeadingZero(usize),
+    /// octet value does not fit into its place
+    OutOfRange(usize),
+}
+
 /// struct for representing ipv6 addresses
 ///
 /// # Example
@@ -76,10 +132,40 @@ pub struct Ipv4 {
 ///
 /// assert_eq!(ip_octets, [0; IPV6_LEN])
 /// ```
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Ipv6 {
     octets: [u8; IPV6_LEN],
 }
 
+/// error returned when parsing ipv6 address from string, groups are counted from zero
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ipv6ParseError {
+    /// input string is empty
+    Empty,
+    /// address has wrong count of 16-bit groups
+    GroupCount(usize),
+    /// group with this index is empty, too long or is not hexadecimal
+    InvalidGroup(usize),
+    /// `::` appears more than once
+    MultipleDoubleColon,
+    /// trailing embedded ipv4 address is invalid
+    InvalidIpv4(Ipv4ParseError),
+}
+
+/// scope of ipv6 multicast address (RFC 7346)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ipv6MulticastScope {
+    InterfaceLocal,
+    LinkLocal,
+    RealmLocal,
+    AdminLocal,
+    SiteLocal,
+    OrganizationLocal,
+    Global,
+    /// reserved or unassigned scope value
+    Other(u8),
+}
+
 /// struct for representing mac addresses
 ///
 /// # Example
@@ -92,10 +178,42 @@ pub struct Ipv6 {
 ///
 /// assert_eq!(mac_octets, [0xff; MAC_LEN])
 /// ```
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Mac {
     mac_addr: [u8; MAC_LEN],
 }
 
+/// error returned when parsing mac address from string, groups are counted from zero
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MacParseError {
+    /// input string is empty
+    Empty,
+    /// address has wrong count of separated groups
+    GroupCount(usize),
+    /// group with this index has wrong length or is not hexadecimal
+    InvalidGroup(usize),
+}
+
+/// notation used by [`Mac::format`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MacFormat {
+    /// `aa:bb:cc:dd:ee:ff`
+    Colon,
+    /// `aa-bb-cc-dd-ee-ff`
+    Dash,
+    /// `aabb.ccdd.eeff`
+    Dotted,
+    /// `aabbccddeeff`
+    Bare,
+}
+
+/// letter case of hex digits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LetterCase {
+    Lower,
+    Upper,
+}
+
 /// wrapper around type's pointer simple to box or arc smart pointer
 ///
 /// # Example
@@ -112,64 +230,121 @@ pub struct Wrapper<T: ?Sized> {
     pointer: *const T,
 }
 
-/// arp header
+/// arp header, multi-byte fields are stored in network byte order
 #[repr(C)]
 pub struct ArpHeader {
-    pub hardware_type: u16,
-    pub protocol_type: u16,
+    pub hardware_type: U16Be,
+    pub protocol_type: U16Be,
     pub hardware_len: u8,
     pub protocol_len: u8,
-    pub opcode: u16,
+    pub opcode: U16Be,
     pub sender_mac: [u8; MAC_LEN],
     pub sender_ip: [u8; IPV4_LEN],
     pub target_mac: [u8; MAC_LEN],
     pub target_ip: [u8; IPV4_LEN],
 }
 
-/// icmp header
+/// icmp header, multi-byte fields are stored in network byte order
 #[repr(C)]
 pub struct IcmpHeader {
     pub type_: u8,
     pub code: u8,
-    pub check: u16,
-    pub id: u16,
-    pub sq: u16,
+    /// checksum, see [`checksum`] and [`Checksum`]
+    pub check: U16Be,
+    pub id: U16Be,
+    pub sq: U16Be,
 }
 
-/// eth header
+/// eth header, multi-byte fields are stored in network byte order
 #[repr(C)]
 pub struct EthHeader {
     pub dest: [u8; MAC_LEN],
     pub source: [u8; MAC_LEN],
-    pub proto: u16,
+    pub proto: U16Be,
 }
 
-/// ipv4 header
+/// ipv4 header, multi-byte fields are stored in network byte order
 #[repr(C)]
 pub struct Ipv4Header {
     pub verihl: u8,
     pub tos: u8,
-    pub tot_len: u16,
-    pub id: u16,
-    pub frag: u16,
+    pub tot_len: U16Be,
+    pub id: U16Be,
+    pub frag: U16Be,
     pub ttl: u8,
     pub protocol: u8,
-    pub check: u16,
+    /// checksum, see [`checksum`] and [`Checksum`]
+    pub check: U16Be,
     pub saddr: [u8; IPV4_LEN],
     pub daddr: [u8; IPV4_LEN],
 }
 
-/// ipv6 header 
+/// ipv6 header, multi-byte fields are stored in network byte order
 #[repr(C)]
 pub struct Ipv6Header {
-    pub verlab: u32,
-    pub payload: u16,
+    pub verlab: U32Be,
+    pub payload: U16Be,
     pub next: u8,
     pub hop: u8,
     pub src: [u8; IPV6_LEN],
     pub dst: [u8; IPV6_LEN]
 }
 
+/// tcp header without options, multi-byte fields are stored in network byte order
+#[repr(C)]
+pub struct TcpHeader {
+    pub source: U16Be,
+    pub dest: U16Be,
+    pub seq: U32Be,
+    pub ack_seq: U32Be,
+    pub doff: u8,
+    pub flags: u8,
+    pub window: U16Be,
+    /// checksum, see [`checksum`] and [`Checksum`]
+    pub check: U16Be,
+    pub urg_ptr: U16Be,
+}
+
+/// udp header, multi-byte fields are stored in network byte order
+#[repr(C)]
+pub struct UdpHeader {
+    pub source: U16Be,
+    pub dest: U16Be,
+    pub len: U16Be,
+    /// checksum, see [`checksum`] and [`Checksum`]
+    pub check: U16Be,
+}
+
+/// error returned when parsing headers from bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseError {
+    /// buffer is shorter than the header
+    Truncated { needed: usize, available: usize },
+    /// ip version field has unexpected value
+    InvalidVersion(u8),
+    /// header length field is smaller than the fixed header or larger than the packet
+    InvalidHeaderLength(usize),
+    /// total length field is smaller than the header or larger than the buffer
+    InvalidTotalLength(usize),
+    /// option with this kind has invalid length or content
+    InvalidOption(u8),
+    /// arp hardware or protocol address length doesn't match the expected one
+    InvalidAddressLength { hardware: u8, protocol: u8 },
+    /// arp hardware or protocol type isn't ethernet and ipv4
+    InvalidAddressType { hardware: u16, protocol: u16 },
+}
+
+/// error returned when building packets
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuildError {
+    /// output buffer is shorter than the packet
+    BufferTooSmall { needed: usize, available: usize },
+    /// options don't fit into the header
+    OptionsTooLong(usize),
+    /// packet is longer than its length field allows
+    PayloadTooLarge(usize),
+}
+
 impl Prime {
     pub fn is_prime(u: u128) -> bool {
         if u <= 1 {
@@ -204,6 +379,7 @@ impl<T: ?Sized> Wrapper<T> {
     pub fn reference(&self) -> &T {
         unsafe { &*self.pointer }
     }
+    #[allow(clippy::mut_from_ref)]
     pub fn mut_reference(&self) -> &mut T {
         unsafe { &mut *(self.pointer as *mut T) }
     }
@@ -211,28 +387,36 @@ impl<T: ?Sized> Wrapper<T> {
 
 unsafe impl<T: ?Sized> Send for Wrapper<T> {}
 
-impl Clone for Mac {
-    fn clone(&self) -> Self {
-        Self {
-            mac_addr: self.mac_addr.clone(),
-        }
-    }
-}
-
 impl Handle<[u8; MAC_LEN]> for Mac {
     fn from(mac_addr: [u8; MAC_LEN]) -> Self {
         Self { mac_addr }
     }
     fn to(&self) -> [u8; MAC_LEN] {
-        self.mac_addr.clone()
+        self.mac_addr
+    }
+}
+
+impl std::fmt::Debug for Mac {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(self, f)
     }
 }
 
 impl std::fmt::Display for Mac {
+    /// formats mac address as lowercase colon separated octets
+    ///
+    /// # Example
+    /// ```
+    /// use curuam::*;
+    ///
+    /// let mac_addr: Mac = Handle::from([0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f]);
+    ///
+    /// assert_eq!(mac_addr.to_string(), "0a:0b:0c:0d:0e:0f")
+    /// ```
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "{:x}:{:x}:{:x}:{:x}:{:x}:{:x}",
+            "{:02x}:{:02x}:{:02x}:{:02x}:{:02x}:{:02x}",
             self.mac_addr[0],
             self.mac_addr[1],
             self.mac_addr[2],
@@ -243,24 +427,223 @@ impl std::fmt::Display for Mac {
     }
 }
 
-impl Clone for Ipv4 {
-    fn clone(&self) -> Self {
-        Self {
-            octets: self.octets.clone(),
+impl Mac {
+    /// checks if address is `ff:ff:ff:ff:ff:ff`
+    pub fn is_broadcast(&self) -> bool {
+        self.mac_addr == [0xff; MAC_LEN]
+    }
+    /// checks if group bit of the first octet is set, broadcast address is multicast too
+    ///
+    /// # Example
+    /// ```
+    /// use curuam::*;
+    ///
+    /// let multicast: Mac = Handle::from([0x01, 0x00, 0x5e, 0x00, 0x00, 0x01]);
+    /// let local: Mac = Handle::from([0x02, 0x00, 0x00, 0x00, 0x00, 0x01]);
+    ///
+    /// assert!(multicast.is_multicast() && multicast.is_universal());
+    /// assert!(local.is_unicast() && local.is_local())
+    /// ```
+    pub fn is_multicast(&self) -> bool {
+        self.mac_addr[0] & 0x01 != 0
+    }
+    pub fn is_unicast(&self) -> bool {
+        !self.is_multicast()
+    }
+    /// checks if address is universally administered (assigned by vendor)
+    pub fn is_universal(&self) -> bool {
+        !self.is_local()
+    }
+    /// checks if address is locally administered
+    pub fn is_local(&self) -> bool {
+        self.mac_addr[0] & 0x02 != 0
+    }
+    /// converts mac address into modified eui-64 interface identifier (RFC 4291 appendix A),
+    /// `ff:fe` is inserted in the middle and universal/local bit is flipped
+    ///
+    /// # Example
+    /// ```
+    /// use curuam::*;
+    ///
+    /// let mac_addr: Mac = "00:11:22:33:44:55".parse().expect("invalid mac");
+    ///
+    /// assert_eq!(mac_addr.to_eui64(), [0x02, 0x11, 0x22, 0xff, 0xfe, 0x33, 0x44, 0x55])
+    /// ```
+    pub fn to_eui64(&self) -> [u8; EUI64_LEN] {
+        [
+            self.mac_addr[0] ^ 0x02,
+            self.mac_addr[1],
+            self.mac_addr[2],
+            0xff,
+            0xfe,
+            self.mac_addr[3],
+            self.mac_addr[4],
+            self.mac_addr[5],
+        ]
+    }
+    /// extracts mac address from modified eui-64 interface identifier, `None` if there is no `ff:fe` in the middle
+    pub fn from_eui64(interface_id: [u8; EUI64_LEN]) -> Option<Self> {
+        if interface_id[3..5] != [0xff, 0xfe] {
+            return None;
+        }
+
+        Some(Handle::from([
+            interface_id[0] ^ 0x02,
+            interface_id[1],
+            interface_id[2],
+            interface_id[5],
+            interface_id[6],
+            interface_id[7],
+        ]))
+    }
+    /// returns `fe80::/64` link-local address derived from mac address
+    ///
+    /// # Example
+    /// ```
+    /// use curuam::*;
+    ///
+    /// let mac_addr: Mac = "00:11:22:33:44:55".parse().expect("invalid mac");
+    ///
+    /// assert_eq!(mac_addr.to_link_local().to_string(), "fe80::211:22ff:fe33:4455")
+    /// ```
+    pub fn to_link_local(&self) -> Ipv6 {
+        let prefix: Ipv6 = Handle::from([0xfe, 0x80, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+
+        Ipv6::from_mac(&prefix, self)
+    }
+    /// formats mac address in the given notation and letter case
+    ///
+    /// # Example
+    /// ```
+    /// use curuam::*;
+    ///
+    /// let mac_addr: Mac = Handle::from([0xaa, 0xbb, 0xcc, 0xdd, 0xee, 0x0f]);
+    ///
+    /// assert_eq!(mac_addr.format(MacFormat::Colon, LetterCase::Lower), "aa:bb:cc:dd:ee:0f");
+    /// assert_eq!(mac_addr.format(MacFormat::Dash, LetterCase::Upper), "AA-BB-CC-DD-EE-0F");
+    /// assert_eq!(mac_addr.format(MacFormat::Dotted, LetterCase::Lower), "aabb.ccdd.ee0f");
+    /// assert_eq!(mac_addr.format(MacFormat::Bare, LetterCase::Upper), "AABBCCDDEE0F")
+    /// ```
+    pub fn format(&self, format: MacFormat, case: LetterCase) -> String {
+        let mut string: String = String::with_capacity(17);
+
+        for (i, octet) in self.mac_addr.iter().enumerate() {
+            if i != 0 {
+                match format {
+                    MacFormat::Colon => string.push(':'),
+                    MacFormat::Dash => string.push('-'),
+                    MacFormat::Dotted if i % 2 == 0 => string.push('.'),
+                    MacFormat::Dotted | MacFormat::Bare => {}
+                }
+            }
+
+            match case {
+                LetterCase::Lower => string.push_str(&format!("{:02x}", octet)),
+                LetterCase::Upper => string.push_str(&format!("{:02X}", octet)),
+            }
+        }
+
+        string
+    }
+}
+
+impl std::str::FromStr for Mac {
+    type Err = MacParseError;
+
+    /// parses mac address in colon (`aa:bb:cc:dd:ee:ff`), dash (`aa-bb-cc-dd-ee-ff`),
+    /// cisco dotted (`aabb.ccdd.eeff`) or bare hex (`aabbccddeeff`) notation
+    ///
+    /// # Example
+    /// ```
+    /// use curuam::*;
+    ///
+    /// let colon: Mac = "0a:0b:0c:0d:0e:0f".parse().expect("invalid mac");
+    /// let dotted: Mac = "0a0b.0c0d.0e0f".parse().expect("invalid mac");
+    ///
+    /// assert_eq!(Handle::<[u8; MAC_LEN]>::to(&colon), [0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f]);
+    /// assert_eq!(colon.to_string(), dotted.to_string());
+    /// assert_eq!("0a:0b:0c:0d:0e".parse::<Mac>().err(), Some(MacParseError::GroupCount(5)))
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(MacParseError::Empty);
+        }
+
+        let (separator, groups, group_len): (Option<char>, usize, std::ops::RangeInclusive<usize>) =
+            if s.contains(':') {
+                (Some(':'), MAC_LEN, 1..=2)
+            } else if s.contains('-') {
+                (Some('-'), MAC_LEN, 1..=2)
+            } else if s.contains('.') {
+                (Some('.'), 3, 4..=4)
+            } else {
+                (None, 1, 12..=12)
+            };
+
+        let parts: Vec<&str> = match separator {
+            Some(separator) => s.split(separator).collect(),
+            None => vec![s],
+        };
+
+        if parts.len() != groups {
+            return Err(MacParseError::GroupCount(parts.len()));
         }
+
+        let mut mac_addr: [u8; MAC_LEN] = [0; MAC_LEN];
+        let octets_per_group: usize = MAC_LEN / groups;
+
+        for (i, part) in parts.iter().enumerate() {
+            if !group_len.contains(&part.len()) || !part.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Err(MacParseError::InvalidGroup(i));
+            }
+
+            let value: u64 = u64::from_str_radix(part, 16).map_err(|_| MacParseError::InvalidGroup(i))?;
+            let bytes: [u8; 8] = value.to_be_bytes();
+
+            mac_addr[i * octets_per_group..(i + 1) * octets_per_group]
+                .copy_from_slice(&bytes[8 - octets_per_group..]);
+        }
+
+        Ok(Handle::from(mac_addr))
     }
 }
 
+impl TryFrom<&str> for Mac {
+    type Error = MacParseError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl std::fmt::Display for MacParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            Self::Empty => write!(f, "empty mac address"),
+            Self::GroupCount(count) => write!(f, "invalid count of groups: {}", count),
+            Self::InvalidGroup(group) => write!(f, "group {} is not a valid hex number", group),
+        }
+    }
+}
+
+impl std::error::Error for MacParseError {}
+
 impl Handle<[u8; IPV4_LEN]> for Ipv4 {
     fn from(ip_addr: [u8; IPV4_LEN]) -> Self {
         Self { octets: ip_addr }
     }
     fn to(&self) -> [u8; IPV4_LEN] {
-        self.octets.clone()
+        self.octets
     }
 }
 
 
+impl std::fmt::Debug for Ipv4 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(self, f)
+    }
+}
+
 impl std::fmt::Display for Ipv4 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -284,47 +667,585 @@ impl Handle<u32> for Ipv4 {
         ((self.octets[0] as u32) << 24)
             + ((self.octets[1] as u32) << 16)
             + ((self.octets[2] as u32) << 8)
-            + ((self.octets[3] as u32) << 0)
+            + (self.octets[3] as u32)
+    }
+}
+
+impl Ipv4 {
+    /// parses ipv4 address in the legacy `inet_aton` notation
+    ///
+    /// accepts one to four parts separated by dots, each part may be decimal,
+    /// octal (leading `0`) or hexadecimal (leading `0x`), the last part fills all remaining octets
+    ///
+    /// # Example
+    /// ```
+    /// use curuam::*;
+    ///
+    /// let short: Ipv4 = Ipv4::parse_lenient("127.1").expect("invalid ip");
+    /// let octal: Ipv4 = Ipv4::parse_lenient("0300.0250.0x1.01").expect("invalid ip");
+    ///
+    /// assert_eq!(Handle::<[u8; IPV4_LEN]>::to(&short), [127, 0, 0, 1]);
+    /// assert_eq!(Handle::<[u8; IPV4_LEN]>::to(&octal), [192, 168, 1, 1])
+    /// ```
+    pub fn parse_lenient(s: &str) -> Result<Self, Ipv4ParseError> {
+        if s.is_empty() {
+            return Err(Ipv4ParseError::Empty);
+        }
+
+        let parts: Vec<&str> = s.split('.').collect();
+        if parts.len() > IPV4_LEN {
+            return Err(Ipv4ParseError::OctetCount(parts.len()));
+        }
+
+        let mut value: u32 = 0;
+        for (i, part) in parts.iter().enumerate() {
+            let number: u32 = parse_lenient_part(part, i)?;
+
+            if i + 1 < parts.len() {
+                if number > u8::MAX as u32 {
+                    return Err(Ipv4ParseError::OutOfRange(i));
+                }
+                value |= number << (8 * (IPV4_LEN - 1 - i));
+            } else {
+                let bits: usize = 8 * (IPV4_LEN - i);
+                if bits < 32 && number >> bits != 0 {
+                    return Err(Ipv4ParseError::OutOfRange(i));
+                }
+                value |= number;
+            }
+        }
+
+        Ok(Handle::from(value))
+    }
+
+    /// checks if address is `0.0.0.0`
+    pub fn is_unspecified(&self) -> bool {
+        self.octets == [0; IPV4_LEN]
+    }
+    /// checks if address is in `127.0.0.0/8`
+    pub fn is_loopback(&self) -> bool {
+        self.octets[0] == 127
+    }
+    /// checks if address is in one of the RFC 1918 ranges `10.0.0.0/8`, `172.16.0.0/12` or `192.168.0.0/16`
+    ///
+    /// # Example
+    /// ```
+    /// use curuam::*;
+    ///
+    /// let private: Ipv4 = Handle::from([172, 31, 0, 1]);
+    /// let public: Ipv4 = Handle::from([172, 32, 0, 1]);
+    ///
+    /// assert!(private.is_private());
+    /// assert!(!public.is_private())
+    /// ```
+    pub fn is_private(&self) -> bool {
+        matches!(self.octets, [10, ..] | [172, 16..=31, ..] | [192, 168, ..])
+    }
+    /// checks if address is in `169.254.0.0/16`
+    pub fn is_link_local(&self) -> bool {
+        matches!(self.octets, [169, 254, ..])
+    }
+    /// checks if address is in `224.0.0.0/4`
+    pub fn is_multicast(&self) -> bool {
+        self.octets[0] >> 4 == 0b1110
+    }
+    /// checks if address is `255.255.255.255`
+    pub fn is_broadcast(&self) -> bool {
+        self.octets == [255; IPV4_LEN]
+    }
+    /// checks if address is in the carrier-grade nat shared space `100.64.0.0/10` (RFC 6598)
+    pub fn is_shared(&self) -> bool {
+        self.octets[0] == 100 && self.octets[1] & 0b1100_0000 == 64
+    }
+    /// checks if address is in `192.0.2.0/24`, `198.51.100.0/24` or `203.0.113.0/24` (RFC 5737)
+    pub fn is_documentation(&self) -> bool {
+        matches!(self.octets, [192, 0, 2, _] | [198, 51, 100, _] | [203, 0, 113, _])
+    }
+    /// checks if address is in `198.18.0.0/15` (RFC 2544)
+    pub fn is_benchmarking(&self) -> bool {
+        self.octets[0] == 198 && self.octets[1] & 0xfe == 18
+    }
+    /// checks if address is in `240.0.0.0/4` except broadcast address
+    pub fn is_reserved(&self) -> bool {
+        self.octets[0] >> 4 == 0b1111 && !self.is_broadcast()
     }
 }
 
-impl Clone for Ipv6 {
-    fn clone(&self) -> Self {
-        Self { octets: self.octets.clone() }
+fn parse_lenient_part(part: &str, index: usize) -> Result<u32, Ipv4ParseError> {
+    let (digits, radix): (&str, u32) = if let Some(hex) = part
+        .strip_prefix("0x")
+        .or_else(|| part.strip_prefix("0X"))
+    {
+        (hex, 16)
+    } else if part.len() > 1 && part.starts_with('0') {
+        (&part[1..], 8)
+    } else {
+        (part, 10)
+    };
+
+    if digits.is_empty() {
+        return Err(Ipv4ParseError::EmptyOctet(index));
+    }
+
+    let mut number: u32 = 0;
+    for character in digits.chars() {
+        let digit: u32 = character
+            .to_digit(radix)
+            .ok_or(Ipv4ParseError::InvalidCharacter { octet: index, character })?;
+
+        number = number
+            .checked_mul(radix)
+            .and_then(|number| number.checked_add(digit))
+            .ok_or(Ipv4ParseError::OutOfRange(index))?;
+    }
+
+    Ok(number)
+}
+
+impl std::str::FromStr for Ipv4 {
+    type Err = Ipv4ParseError;
+
+    /// parses ipv4 address in the strict dotted-quad notation (`192.168.1.1`)
+    ///
+    /// # Example
+    /// ```
+    /// use curuam::*;
+    ///
+    /// let ip_addr: Ipv4 = "192.168.1.1".parse().expect("invalid ip");
+    ///
+    /// assert_eq!(ip_addr.to_string(), "192.168.1.1");
+    /// assert_eq!("192.168.01.1".parse::<Ipv4>().err(), Some(Ipv4ParseError::LeadingZero(2)));
+    /// assert_eq!("192.168.1.256".parse::<Ipv4>().err(), Some(Ipv4ParseError::OutOfRange(3)))
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(Ipv4ParseError::Empty);
+        }
+
+        let mut octets: [u8; IPV4_LEN] = [0; IPV4_LEN];
+        let mut count: usize = 0;
+
+        for (i, part) in s.split('.').enumerate() {
+            count += 1;
+            if i >= IPV4_LEN {
+                continue;
+            }
+
+            if part.is_empty() {
+                return Err(Ipv4ParseError::EmptyOctet(i));
+            }
+
+            let mut octet: u16 = 0;
+            for character in part.chars() {
+                let digit: u32 = character
+                    .to_digit(10)
+                    .ok_or(Ipv4ParseError::InvalidCharacter { octet: i, character })?;
+
+                octet = octet * 10 + digit as u16;
+                if octet > u8::MAX as u16 {
+                    return Err(Ipv4ParseError::OutOfRange(i));
+                }
+            }
+
+            if part.len() > 1 && part.starts_with('0') {
+                return Err(Ipv4ParseError::LeadingZero(i));
+            }
+
+            octets[i] = octet as u8;
+        }
+
+        if count != IPV4_LEN {
+            return Err(Ipv4ParseError::OctetCount(count));
+        }
+
+        Ok(Handle::from(octets))
+    }
+}
+
+impl TryFrom<&str> for Ipv4 {
+    type Error = Ipv4ParseError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl std::fmt::Display for Ipv4ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            Self::Empty => write!(f, "empty ipv4 address"),
+            Self::OctetCount(count) => write!(f, "expected {} octets, found {}", IPV4_LEN, count),
+            Self::EmptyOctet(octet) => write!(f, "octet {} is empty", octet),
+            Self::InvalidCharacter { octet, character } => {
+                write!(f, "invalid character {:?} in octet {}", character, octet)
+            }
+            Self::LeadingZero(octet) => write!(f, "octet {} has a leading zero", octet),
+            Self::OutOfRange(octet) => write!(f, "octet {} is out of range", octet),
+        }
     }
 }
 
+impl std::error::Error for Ipv4ParseError {}
+
 impl Handle<[u8; IPV6_LEN]> for Ipv6 {
     fn from(octets: [u8; IPV6_LEN]) -> Self {
         Self { octets }
     }
     fn to(&self) -> [u8; IPV6_LEN] {
-        self.octets.clone()
+        self.octets
+    }
+}
+
+impl Handle<u128> for Ipv6 {
+    fn from(value: u128) -> Self {
+        Handle::from(value.to_be_bytes())
+    }
+    fn to(&self) -> u128 {
+        u128::from_be_bytes(self.octets)
+    }
+}
+
+impl Ipv6 {
+    /// checks if address is `::`
+    pub fn is_unspecified(&self) -> bool {
+        self.octets == [0; IPV6_LEN]
+    }
+    /// checks if address is `::1`
+    pub fn is_loopback(&self) -> bool {
+        Handle::<u128>::to(self) == 1
+    }
+    /// checks if address is in `fc00::/7` (RFC 4193)
+    pub fn is_unique_local(&self) -> bool {
+        self.octets[0] & 0xfe == 0xfc
+    }
+    /// checks if address is in `fe80::/10`
+    pub fn is_link_local(&self) -> bool {
+        self.octets[0] == 0xfe && self.octets[1] & 0xc0 == 0x80
+    }
+    /// checks if address is in `ff00::/8`
+    pub fn is_multicast(&self) -> bool {
+        self.octets[0] == 0xff
+    }
+    /// returns scope of multicast address, `None` if address is not multicast
+    ///
+    /// # Example
+    /// ```
+    /// use curuam::*;
+    ///
+    /// let all_nodes: Ipv6 = "ff02::1".parse().expect("invalid ip");
+    /// let link_local: Ipv6 = "fe80::1".parse().expect("invalid ip");
+    ///
+    /// assert_eq!(all_nodes.multicast_scope(), Some(Ipv6MulticastScope::LinkLocal));
+    /// assert_eq!(link_local.multicast_scope(), None)
+    /// ```
+    pub fn multicast_scope(&self) -> Option<Ipv6MulticastScope> {
+        if !self.is_multicast() {
+            return None;
+        }
+
+        Some(Handle::from(self.octets[1] & 0x0f))
+    }
+    /// checks if address is in `::ffff:0:0/96`
+    pub fn is_ipv4_mapped(&self) -> bool {
+        self.octets[..10] == [0; 10] && self.octets[10..12] == [0xff, 0xff]
+    }
+    /// checks if address is in the deprecated `::/96` range, `::` and `::1` are excluded
+    pub fn is_ipv4_compatible(&self) -> bool {
+        self.octets[..12] == [0; 12] && !self.is_unspecified() && !self.is_loopback()
+    }
+    /// returns embedded ipv4 address of ipv4-mapped or ipv4-compatible address
+    ///
+    /// # Example
+    /// ```
+    /// use curuam::*;
+    ///
+    /// let mapped: Ipv6 = "::ffff:10.0.0.1".parse().expect("invalid ip");
+    ///
+    /// assert_eq!(mapped.to_ipv4().expect("not ipv4").to_string(), "10.0.0.1")
+    /// ```
+    pub fn to_ipv4(&self) -> Option<Ipv4> {
+        if !self.is_ipv4_mapped() && !self.is_ipv4_compatible() {
+            return None;
+        }
+
+        let mut octets: [u8; IPV4_LEN] = [0; IPV4_LEN];
+        octets.copy_from_slice(&self.octets[12..]);
+
+        Some(Handle::from(octets))
+    }
+    /// combines upper 64 bits of the prefix with modified eui-64 interface identifier of mac address,
+    /// this is the address chosen by slaac for a /64 prefix
+    ///
+    /// # Example
+    /// ```
+    /// use curuam::*;
+    ///
+    /// let prefix: Ipv6 = "2001:db8:1:2::".parse().expect("invalid ip");
+    /// let mac_addr: Mac = "00:11:22:33:44:55".parse().expect("invalid mac");
+    ///
+    /// let ip_addr: Ipv6 = Ipv6::from_mac(&prefix, &mac_addr);
+    ///
+    /// assert_eq!(ip_addr.to_string(), "2001:db8:1:2:211:22ff:fe33:4455");
+    /// assert_eq!(ip_addr.to_mac().expect("not eui-64").to_string(), "00:11:22:33:44:55")
+    /// ```
+    pub fn from_mac(prefix: &Ipv6, mac_addr: &Mac) -> Self {
+        let mut octets: [u8; IPV6_LEN] = prefix.octets;
+        octets[IPV6_LEN - EUI64_LEN..].copy_from_slice(&mac_addr.to_eui64());
+
+        Handle::from(octets)
+    }
+    /// returns lower 64 bits of the address
+    pub fn interface_id(&self) -> [u8; EUI64_LEN] {
+        let mut interface_id: [u8; EUI64_LEN] = [0; EUI64_LEN];
+        interface_id.copy_from_slice(&self.octets[IPV6_LEN - EUI64_LEN..]);
+
+        interface_id
+    }
+    /// extracts mac address from eui-64 based address, `None` if interface identifier is not eui-64 based
+    pub fn to_mac(&self) -> Option<Mac> {
+        Mac::from_eui64(self.interface_id())
+    }
+    /// checks if address is in `2001:db8::/32` (RFC 3849) or `3fff::/20` (RFC 9637)
+    ///
+    /// # Example
+    /// ```
+    /// use curuam::*;
+    ///
+    /// let inside: Ipv6 = "3fff:fff::1".parse().expect("invalid ip");
+    /// let outside: Ipv6 = "3fff:1000::1".parse().expect("invalid ip");
+    /// let other: Ipv6 = "3ff0::1".parse().expect("invalid ip");
+    ///
+    /// assert!(inside.is_documentation());
+    /// assert!(!outside.is_documentation());
+    /// assert!(!other.is_documentation())
+    /// ```
+    pub fn is_documentation(&self) -> bool {
+        matches!(self.octets, [0x20, 0x01, 0x0d, 0xb8, ..])
+            || (self.octets[0] == 0x3f && self.octets[1] == 0xff && self.octets[2] & 0xf0 == 0)
+    }
+}
+
+impl Handle<u8> for Ipv6MulticastScope {
+    fn from(value: u8) -> Self {
+        match value {
+            0x1 => Self::InterfaceLocal,
+            0x2 => Self::LinkLocal,
+            0x3 => Self::RealmLocal,
+            0x4 => Self::AdminLocal,
+            0x5 => Self::SiteLocal,
+            0x8 => Self::OrganizationLocal,
+            0xe => Self::Global,
+            value => Self::Other(value),
+        }
+    }
+    fn to(&self) -> u8 {
+        match *self {
+            Self::InterfaceLocal => 0x1,
+            Self::LinkLocal => 0x2,
+            Self::RealmLocal => 0x3,
+            Self::AdminLocal => 0x4,
+            Self::SiteLocal => 0x5,
+            Self::OrganizationLocal => 0x8,
+            Self::Global => 0xe,
+            Self::Other(value) => value,
+        }
+    }
+}
+
+impl std::fmt::Debug for Ipv6 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(self, f)
     }
 }
 
 impl std::fmt::Display for Ipv6 {
+    /// formats ipv6 address in the canonical text form described in RFC 5952
+    ///
+    /// # Example
+    /// ```
+    /// use curuam::*;
+    ///
+    /// let ip_addr: Ipv6 = Handle::from([0x20, 0x01, 0x0d, 0xb8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x01]);
+    /// let mapped: Ipv6 = Handle::from([0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0xff, 0xff, 192, 168, 1, 1]);
+    ///
+    /// assert_eq!(ip_addr.to_string(), "2001:db8::1");
+    /// assert_eq!(mapped.to_string(), "::ffff:192.168.1.1")
+    /// ```
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{:x}{:x}:{:x}{:x}:{:x}{:x}:{:x}{:x}:{:x}{:x}:{:x}{:x}:{:x}{:x}:{:x}{:x}",
-            self.octets[0],
-            self.octets[1],
-            self.octets[2],
-            self.octets[3],
-            self.octets[4],
-            self.octets[5],
-            self.octets[6],
-            self.octets[7],
-            self.octets[8],
-            self.octets[9],
-            self.octets[10],
-            self.octets[11],
-            self.octets[12],
-            self.octets[13],
-            self.octets[14],
-            self.octets[15]
-        )
+        if self.is_ipv4_mapped() {
+            return write!(
+                f,
+                "::ffff:{}.{}.{}.{}",
+                self.octets[12], self.octets[13], self.octets[14], self.octets[15]
+            );
+        }
+
+        let mut groups: [u16; 8] = [0; 8];
+        for (i, group) in groups.iter_mut().enumerate() {
+            *group = u16::from_be_bytes([self.octets[2 * i], self.octets[2 * i + 1]]);
+        }
+
+        // the first longest run of at least two zero groups is collapsed to `::`
+        let mut zeros: (usize, usize) = (0, 0);
+        let mut i: usize = 0;
+        while i < groups.len() {
+            let start: usize = i;
+            while i < groups.len() && groups[i] == 0 {
+                i += 1;
+            }
+
+            if i - start > zeros.1 {
+                zeros = (start, i - start);
+            }
+            i += 1;
+        }
+
+        if zeros.1 < 2 {
+            zeros = (groups.len(), 0);
+        }
+
+        for (i, group) in groups.iter().enumerate() {
+            if i == zeros.0 {
+                write!(f, "::")?;
+            }
+            if i >= zeros.0 && i < zeros.0 + zeros.1 {
+                continue;
+            }
+            if i != 0 && i != zeros.0 + zeros.1 {
+                write!(f, ":")?;
+            }
+            write!(f, "{:x}", group)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::str::FromStr for Ipv6 {
+    type Err = Ipv6ParseError;
+
+    /// parses ipv6 address in any text form described in RFC 4291
+    ///
+    /// # Example
+    /// ```
+    /// use curuam::*;
+    ///
+    /// let full: Ipv6 = "2001:0DB8:0000:0000:0000:0000:0000:0001".parse().expect("invalid ip");
+    /// let mapped: Ipv6 = "::ffff:1.2.3.4".parse().expect("invalid ip");
+    ///
+    /// assert_eq!(full.to_string(), "2001:db8::1");
+    /// assert_eq!(
+    ///     Handle::<[u8; IPV6_LEN]>::to(&mapped),
+    ///     [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0xff, 0xff, 1, 2, 3, 4]
+    /// );
+    /// assert_eq!("1::2::3".parse::<Ipv6>().err(), Some(Ipv6ParseError::MultipleDoubleColon))
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(Ipv6ParseError::Empty);
+        }
+
+        let (head, tail): (&str, Option<&str>) = match s.find("::") {
+            Some(i) => (&s[..i], Some(&s[i + 2..])),
+            None => (s, None),
+        };
+
+        if tail.is_some_and(|tail| tail.contains("::")) {
+            return Err(Ipv6ParseError::MultipleDoubleColon);
+        }
+
+        let mut head_octets: Vec<u8> = Vec::with_capacity(IPV6_LEN);
+        let mut tail_octets: Vec<u8> = Vec::with_capacity(IPV6_LEN);
+
+        let head_groups: usize = parse_ipv6_groups(head, tail.is_none(), 0, &mut head_octets)?;
+        let groups: usize = match tail {
+            Some(tail) => head_groups + parse_ipv6_groups(tail, true, head_groups, &mut tail_octets)?,
+            None => head_groups,
+        };
+
+        // `::` must replace at least one group
+        if (tail.is_some() && groups >= 8) || (tail.is_none() && groups != 8) {
+            return Err(Ipv6ParseError::GroupCount(groups));
+        }
+
+        let mut octets: [u8; IPV6_LEN] = [0; IPV6_LEN];
+        octets[..head_octets.len()].copy_from_slice(&head_octets);
+        octets[IPV6_LEN - tail_octets.len()..].copy_from_slice(&tail_octets);
+
+        Ok(Handle::from(octets))
+    }
+}
+
+/// parses colon separated groups, an embedded ipv4 address is allowed only at the end of the address
+fn parse_ipv6_groups(
+    s: &str,
+    is_last: bool,
+    first_group: usize,
+    octets: &mut Vec<u8>,
+) -> Result<usize, Ipv6ParseError> {
+    if s.is_empty() {
+        return Ok(0);
+    }
+
+    let parts: Vec<&str> = s.split(':').collect();
+    let mut groups: usize = 0;
+
+    for (i, part) in parts.iter().enumerate() {
+        let index: usize = first_group + groups;
+
+        if is_last && i + 1 == parts.len() && part.contains('.') {
+            let ipv4: Ipv4 = part.parse().map_err(Ipv6ParseError::InvalidIpv4)?;
+            octets.extend_from_slice(&ipv4.octets);
+            groups += 2;
+            break;
+        }
+
+        if part.is_empty() || part.len() > 4 || !part.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(Ipv6ParseError::InvalidGroup(index));
+        }
+
+        let group: u16 = u16::from_str_radix(part, 16).map_err(|_| Ipv6ParseError::InvalidGroup(index))?;
+        octets.extend_from_slice(&group.to_be_bytes());
+        groups += 1;
+
+        if groups > 8 {
+            return Err(Ipv6ParseError::GroupCount(first_group + groups));
+        }
+    }
+
+    if first_group + groups > 8 {
+        return Err(Ipv6ParseError::GroupCount(first_group + groups));
+    }
+
+    Ok(groups)
+}
+
+impl TryFrom<&str> for Ipv6 {
+    type Error = Ipv6ParseError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl std::fmt::Display for Ipv6ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            Self::Empty => write!(f, "empty ipv6 address"),
+            Self::GroupCount(count) => write!(f, "invalid count of groups: {}", count),
+            Self::InvalidGroup(group) => write!(f, "group {} is not a valid 16-bit hex number", group),
+            Self::MultipleDoubleColon => write!(f, "`::` may appear only once"),
+            Self::InvalidIpv4(err) => write!(f, "invalid embedded ipv4 address: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for Ipv6ParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InvalidIpv4(err) => Some(err),
+            _ => None,
+        }
     }
 }
 
@@ -373,7 +1294,7 @@ fn power_with_start(start: f64, f: f64, power: u16) -> f64 {
 /// ```
 pub fn memcpy<TD, TS>(dest: *mut TD, src: *const TS, size: usize) -> *mut TD {
     if dest as usize == 0 {
-        return 0 as *mut TD;
+        return std::ptr::null_mut();
     }
 
     let byte_dest: *mut u8 = dest as *mut u8;
@@ -402,7 +1323,7 @@ pub fn str_from_bytes(bytes: &[u8]) -> String {
     let mut string: String = String::new();
 
     for byte in bytes {
-        string.push(byte.clone() as char)
+        string.push(*byte as char)
     }
 
     string
@@ -474,7 +1395,7 @@ pub fn random_with_seed(seed: RandomNumber) -> RandomNumber {
         }
 
         seed = ((seed*MULTIPLIER)+ADDER)%power(2f64, SEED_OFFSET as u16) as RandomNumber;
-        result += seed << 8*i;
+        result += seed << (8*i);
 
         i += 1;
     }
@@ -487,29 +1408,119 @@ pub fn random_in_range(min: RandomNumber, max: RandomNumber) -> Result<RandomNum
     Ok(random_with_seed(unix_epoch.as_nanos() as RandomNumber)%(max-min)+min)
 }
 
+/// computes internet checksum of `len` bytes at `header`, the value is in host order,
+/// so store it with `U16Be::new` (or `to_be_bytes`) like the other header fields
+///
+/// before 0.3.0 the value was a native order word meant to be written to the header as is
+///
+/// # Example
+/// ```
+/// use curuam::*;
+///
+/// let mut header = IcmpHeader {
+///     type_: 8,
+///     code: 0,
+///     check: U16Be::new(0),
+///     id: U16Be::new(1),
+///     sq: U16Be::new(1),
+/// };
+///
+/// let bytes: &[u8] = header.as_bytes();
+/// let check: u16 = checksum(bytes.as_ptr(), bytes.len());
+/// header.check = U16Be::new(check);
+///
+/// assert_eq!(header.as_bytes()[2..4], [0xf7, 0xfd]);
+/// assert_eq!(Checksum::compute(header.as_bytes()), 0)
+/// ```
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
 pub fn checksum(header: *const u8, len: usize) -> u16 {
-    let mut sum: i32 = 0;
-    let mut left: usize = len;
-    let words: *const u16 = header as *const u16; 
+    // SAFETY: caller passes pointer to `len` readable bytes, as before
+    Checksum::compute(unsafe { std::slice::from_raw_parts(header, len) })
+}
 
-    let mut i: usize = 0;
-    while left > 1 {
-        sum += unsafe {
-            *((words as usize + i) as *const u16)
-        } as i32;
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            Self::Truncated { needed, available } => {
+                write!(f, "buffer is truncated: needed {} bytes, available {}", needed, available)
+            }
+            Self::InvalidVersion(version) => write!(f, "invalid ip version {}", version),
+            Self::InvalidHeaderLength(len) => write!(f, "invalid header length {}", len),
+            Self::InvalidTotalLength(len) => write!(f, "invalid total length {}", len),
+            Self::InvalidOption(kind) => write!(f, "invalid option of kind {}", kind),
+            Self::InvalidAddressLength { hardware, protocol } => {
+                write!(f, "invalid address length: hardware {}, protocol {}", hardware, protocol)
+            }
+            Self::InvalidAddressType { hardware, protocol } => {
+                write!(f, "invalid address type: hardware {:#06x}, protocol {:#06x}", hardware, protocol)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
 
-        left -= 2;
-        i += 2
+impl std::fmt::Display for BuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            Self::BufferTooSmall { needed, available } => {
+                write!(f, "buffer is too small: needed {} bytes, available {}", needed, available)
+            }
+            Self::OptionsTooLong(len) => write!(f, "options are too long: {} bytes", len),
+            Self::PayloadTooLarge(len) => write!(f, "payload is too large: {} bytes", len),
+        }
     }
+}
+
+impl std::error::Error for BuildError {}
+
+/// headers which can be read from bytes without copying
+///
+/// # Safety
+/// implementor must be `repr(C)` with alignment 1 and every bit pattern must be valid for it
+pub(crate) unsafe trait FromBytes: Sized {
+    /// splits bytes into header reference and the rest
+    fn ref_from(bytes: &[u8]) -> Result<(&Self, &[u8]), ParseError> {
+        const { assert!(std::mem::align_of::<Self>() == 1) };
+
+        let size: usize = std::mem::size_of::<Self>();
+        if bytes.len() < size {
+            return Err(ParseError::Truncated {
+                needed: size,
+                available: bytes.len(),
+            });
+        }
 
-    if left == 1 {            
-        sum += unsafe {
-            *((words as usize + i - 1) as *const u8)
-        } as i32;
+        let (header, rest): (&[u8], &[u8]) = bytes.split_at(size);
+
+        // SAFETY: length is checked above, alignment and validity are guaranteed by implementor
+        Ok((unsafe { &*(header.as_ptr() as *const Self) }, rest))
+    }
+    /// returns header as it would be on the wire
+    fn as_bytes(&self) -> &[u8] {
+        // SAFETY: alignment 1 means there is no padding, so every byte is initialized
+        unsafe { std::slice::from_raw_parts(self as *const Self as *const u8, std::mem::size_of::<Self>()) }
     }
+}
+
+macro_rules! impl_from_bytes {
+    ($($type:ident),*) => {
+        $(
+            unsafe impl FromBytes for $type {}
 
-    sum = (sum >> 16) + (sum & 0xffff); 
-    sum += sum >> 16;
+            impl $type {
+                /// returns header as it would be on the wire
+                pub fn as_bytes(&self) -> &[u8] {
+                    FromBytes::as_bytes(self)
+                }
+            }
+        )*
+    };
+}
 
-    (!sum) as u16
-}
\ No newline at end of file
+impl_from_bytes!(ArpHeader, IcmpHeader, EthHeader, Ipv4Header, Ipv6Header, TcpHeader, UdpHeader);
+
+/// reads big-endian u32 at the offset, caller checks the bounds
+pub(crate) fn u32_at(bytes: &[u8], offset: usize) -> u32 {
+    u32::from_be_bytes([bytes[offset], bytes[offset + 1], bytes[offset + 2], bytes[offset + 3]])
+}
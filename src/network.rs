@@ -0,0 +1,541 @@
+use crate::{Handle, Ipv4, Ipv4ParseError, Ipv6, Ipv6ParseError, IPV4_LEN, IPV6_LEN};
+
+const IPV4_BITS: u8 = 8 * IPV4_LEN as u8;
+const IPV6_BITS: u8 = 8 * IPV6_LEN as u8;
+
+/// ipv4 network in cidr notation, host bits of the address are always cleared
+///
+/// # Example
+/// ```
+/// use curuam::*;
+///
+/// let network: Ipv4Network = "192.168.1.0/24".parse().expect("invalid network");
+/// let ip_addr: Ipv4 = "192.168.1.42".parse().expect("invalid ip");
+///
+/// assert!(network.contains(&ip_addr));
+/// assert_eq!(network.broadcast().to_string(), "192.168.1.255");
+/// assert_eq!(network.netmask().to_string(), "255.255.255.0");
+/// assert_eq!(network.host_count(), 254)
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Ipv4Network {
+    network: u32,
+    prefix: u8,
+}
+
+/// lazy iterator over usable host addresses of [`Ipv4Network`]
+pub struct Ipv4Hosts {
+    next: u64,
+    end: u64,
+}
+
+/// lazy iterator over subnets of [`Ipv4Network`]
+pub struct Ipv4Subnets {
+    next: u64,
+    end: u64,
+    prefix: u8,
+}
+
+/// ipv6 network prefix, host bits of the address are always cleared
+///
+/// # Example
+/// ```
+/// use curuam::*;
+///
+/// let network: Ipv6Network = "2001:db8::/32".parse().expect("invalid network");
+/// let ip_addr: Ipv6 = "2001:db8:1::1".parse().expect("invalid ip");
+///
+/// assert!(network.contains(&ip_addr));
+/// assert_eq!(network.netmask().to_string(), "ffff:ffff::");
+/// assert_eq!(network.supernet().expect("no supernet").to_string(), "2001:db8::/31")
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Ipv6Network {
+    network: u128,
+    prefix: u8,
+}
+
+/// lazy iterator over subnets of [`Ipv6Network`]
+pub struct Ipv6Subnets {
+    next: Option<u128>,
+    last: u128,
+    prefix: u8,
+}
+
+/// error returned when building or parsing network
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NetworkError {
+    /// string has no `/` separated prefix
+    MissingPrefix,
+    /// prefix is not a decimal number or has leading zero
+    InvalidPrefix,
+    /// prefix is longer than the address or shorter than the parent network
+    PrefixOutOfRange(u32),
+    /// netmask has non contiguous bits
+    InvalidNetmask,
+    /// ipv4 address part is invalid
+    InvalidIpv4(Ipv4ParseError),
+    /// ipv6 address part is invalid
+    InvalidIpv6(Ipv6ParseError),
+}
+
+impl Ipv4Network {
+    /// creates network from any address inside of it and prefix length
+    pub fn new(ip_addr: Ipv4, prefix: u8) -> Result<Self, NetworkError> {
+        if prefix > IPV4_BITS {
+            return Err(NetworkError::PrefixOutOfRange(prefix.into()));
+        }
+
+        let address: u32 = Handle::<u32>::to(&ip_addr);
+
+        Ok(Self {
+            network: address & ipv4_mask(prefix),
+            prefix,
+        })
+    }
+    /// creates network from any address inside of it and netmask like `255.255.255.0`
+    ///
+    /// # Example
+    /// ```
+    /// use curuam::*;
+    ///
+    /// let ip_addr: Ipv4 = Handle::from([10, 1, 2, 3]);
+    /// let netmask: Ipv4 = Handle::from([255, 255, 0, 0]);
+    ///
+    /// let network = Ipv4Network::with_netmask(ip_addr, netmask).expect("invalid netmask");
+    ///
+    /// assert_eq!(network.to_string(), "10.1.0.0/16")
+    /// ```
+    pub fn with_netmask(ip_addr: Ipv4, netmask: Ipv4) -> Result<Self, NetworkError> {
+        let mask: u32 = Handle::<u32>::to(&netmask);
+        let prefix: u8 = mask.leading_ones() as u8;
+
+        if ipv4_mask(prefix) != mask {
+            return Err(NetworkError::InvalidNetmask);
+        }
+
+        Self::new(ip_addr, prefix)
+    }
+    pub fn prefix(&self) -> u8 {
+        self.prefix
+    }
+    pub fn network(&self) -> Ipv4 {
+        Handle::from(self.network)
+    }
+    pub fn broadcast(&self) -> Ipv4 {
+        Handle::from(self.network | !ipv4_mask(self.prefix))
+    }
+    pub fn netmask(&self) -> Ipv4 {
+        Handle::from(ipv4_mask(self.prefix))
+    }
+    pub fn hostmask(&self) -> Ipv4 {
+        Handle::from(!ipv4_mask(self.prefix))
+    }
+    pub fn contains(&self, ip_addr: &Ipv4) -> bool {
+        Handle::<u32>::to(ip_addr) & ipv4_mask(self.prefix) == self.network
+    }
+    /// network with prefix shorter by one, `None` for /0
+    pub fn supernet(&self) -> Option<Self> {
+        let prefix: u8 = self.prefix.checked_sub(1)?;
+
+        Some(Self {
+            network: self.network & ipv4_mask(prefix),
+            prefix,
+        })
+    }
+    /// checks if every address of this network is inside of the other network
+    pub fn is_subnet_of(&self, other: &Self) -> bool {
+        self.prefix >= other.prefix && other.contains(&self.network())
+    }
+    pub fn is_supernet_of(&self, other: &Self) -> bool {
+        other.is_subnet_of(self)
+    }
+    /// checks if networks have at least one common address
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.is_subnet_of(other) || other.is_subnet_of(self)
+    }
+    /// count of all addresses in the network including network and broadcast addresses
+    pub fn size(&self) -> u64 {
+        1 << (IPV4_BITS - self.prefix)
+    }
+    /// count of addresses returned by [`Ipv4Network::hosts`]
+    pub fn host_count(&self) -> u64 {
+        match self.prefix {
+            31 | 32 => self.size(),
+            _ => self.size() - 2,
+        }
+    }
+    /// iterates over usable host addresses, network and broadcast addresses are skipped
+    /// except for /31 (RFC 3021) and /32 networks
+    ///
+    /// # Example
+    /// ```
+    /// use curuam::*;
+    ///
+    /// let network: Ipv4Network = "10.0.0.0/30".parse().expect("invalid network");
+    /// let point_to_point: Ipv4Network = "10.0.0.0/31".parse().expect("invalid network");
+    ///
+    /// let hosts: Vec<String> = network.hosts().map(|host| host.to_string()).collect();
+    ///
+    /// assert_eq!(hosts, ["10.0.0.1", "10.0.0.2"]);
+    /// assert_eq!(point_to_point.hosts().count(), 2)
+    /// ```
+    pub fn hosts(&self) -> Ipv4Hosts {
+        let start: u64 = self.network as u64;
+        let end: u64 = start + self.size();
+
+        match self.prefix {
+            31 | 32 => Ipv4Hosts { next: start, end },
+            _ => Ipv4Hosts {
+                next: start + 1,
+                end: end - 1,
+            },
+        }
+    }
+    /// splits network into subnets with longer prefix
+    ///
+    /// # Example
+    /// ```
+    /// use curuam::*;
+    ///
+    /// let network: Ipv4Network = "10.0.0.0/24".parse().expect("invalid network");
+    ///
+    /// let subnets: Vec<String> = network
+    ///     .subnets(26)
+    ///     .expect("invalid prefix")
+    ///     .map(|subnet| subnet.to_string())
+    ///     .collect();
+    ///
+    /// assert_eq!(subnets, ["10.0.0.0/26", "10.0.0.64/26", "10.0.0.128/26", "10.0.0.192/26"])
+    /// ```
+    pub fn subnets(&self, prefix: u8) -> Result<Ipv4Subnets, NetworkError> {
+        if prefix < self.prefix || prefix > IPV4_BITS {
+            return Err(NetworkError::PrefixOutOfRange(prefix.into()));
+        }
+
+        let start: u64 = self.network as u64;
+
+        Ok(Ipv4Subnets {
+            next: start,
+            end: start + self.size(),
+            prefix,
+        })
+    }
+}
+
+impl Ipv6Network {
+    /// creates network from any address inside of it and prefix length
+    pub fn new(ip_addr: Ipv6, prefix: u8) -> Result<Self, NetworkError> {
+        if prefix > IPV6_BITS {
+            return Err(NetworkError::PrefixOutOfRange(prefix.into()));
+        }
+
+        let address: u128 = Handle::<u128>::to(&ip_addr);
+
+        Ok(Self {
+            network: address & ipv6_mask(prefix),
+            prefix,
+        })
+    }
+    pub fn prefix(&self) -> u8 {
+        self.prefix
+    }
+    pub fn network(&self) -> Ipv6 {
+        Handle::from(self.network)
+    }
+    /// last address of the network
+    pub fn last(&self) -> Ipv6 {
+        Handle::from(self.network | !ipv6_mask(self.prefix))
+    }
+    pub fn netmask(&self) -> Ipv6 {
+        Handle::from(ipv6_mask(self.prefix))
+    }
+    pub fn hostmask(&self) -> Ipv6 {
+        Handle::from(!ipv6_mask(self.prefix))
+    }
+    pub fn contains(&self, ip_addr: &Ipv6) -> bool {
+        Handle::<u128>::to(ip_addr) & ipv6_mask(self.prefix) == self.network
+    }
+    /// network with prefix shorter by one, `None` for /0
+    pub fn supernet(&self) -> Option<Self> {
+        let prefix: u8 = self.prefix.checked_sub(1)?;
+
+        Some(Self {
+            network: self.network & ipv6_mask(prefix),
+            prefix,
+        })
+    }
+    /// checks if every address of this network is inside of the other network
+    ///
+    /// # Example
+    /// ```
+    /// use curuam::*;
+    ///
+    /// let site: Ipv6Network = "2001:db8:aa00::/40".parse().expect("invalid network");
+    /// let lan: Ipv6Network = "2001:db8:aa01::/64".parse().expect("invalid network");
+    /// let other: Ipv6Network = "2001:db8:bb00::/40".parse().expect("invalid network");
+    ///
+    /// assert!(lan.is_subnet_of(&site));
+    /// assert!(site.overlaps(&lan));
+    /// assert!(!site.overlaps(&other))
+    /// ```
+    pub fn is_subnet_of(&self, other: &Self) -> bool {
+        self.prefix >= other.prefix && other.contains(&self.network())
+    }
+    pub fn is_supernet_of(&self, other: &Self) -> bool {
+        other.is_subnet_of(self)
+    }
+    /// checks if networks have at least one common address
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.is_subnet_of(other) || other.is_subnet_of(self)
+    }
+    /// splits network into child prefixes with longer prefix
+    ///
+    /// # Example
+    /// ```
+    /// use curuam::*;
+    ///
+    /// let network: Ipv6Network = "2001:db8:0:100::/56".parse().expect("invalid network");
+    ///
+    /// let mut subnets = network.subnets(64).expect("invalid prefix");
+    /// let all: Ipv6Network = "::/0".parse().expect("invalid network");
+    ///
+    /// assert_eq!(subnets.size_hint(), (256, Some(256)));
+    /// assert_eq!(all.subnets(128).expect("invalid prefix").size_hint(), (usize::MAX, None));
+    /// assert_eq!(subnets.next().expect("no subnet").to_string(), "2001:db8:0:100::/64");
+    /// assert_eq!(subnets.next().expect("no subnet").to_string(), "2001:db8:0:101::/64");
+    /// assert_eq!(subnets.last().expect("no subnet").to_string(), "2001:db8:0:1ff::/64")
+    /// ```
+    pub fn subnets(&self, prefix: u8) -> Result<Ipv6Subnets, NetworkError> {
+        if prefix < self.prefix || prefix > IPV6_BITS {
+            return Err(NetworkError::PrefixOutOfRange(prefix.into()));
+        }
+
+        Ok(Ipv6Subnets {
+            next: Some(self.network),
+            last: (self.network | !ipv6_mask(self.prefix)) & ipv6_mask(prefix),
+            prefix,
+        })
+    }
+}
+
+fn ipv4_mask(prefix: u8) -> u32 {
+    u32::MAX.checked_shl((IPV4_BITS - prefix) as u32).unwrap_or(0)
+}
+
+fn ipv6_mask(prefix: u8) -> u128 {
+    u128::MAX.checked_shl((IPV6_BITS - prefix) as u32).unwrap_or(0)
+}
+
+/// splits `address/prefix` string, prefix is returned as a string
+pub(crate) fn split_prefix(s: &str) -> Result<(&str, &str), NetworkError> {
+    s.split_once('/').ok_or(NetworkError::MissingPrefix)
+}
+
+pub(crate) fn parse_prefix(s: &str, max: u8) -> Result<u8, NetworkError> {
+    // leading zero is rejected like in ipv4 octets
+    if s.is_empty() || !s.chars().all(|c| c.is_ascii_digit()) || (s.len() > 1 && s.starts_with('0')) {
+        return Err(NetworkError::InvalidPrefix);
+    }
+
+    // only digits are left, so parsing can fail on overflow only
+    let prefix: u32 = s.parse().unwrap_or(u32::MAX);
+    if prefix > max as u32 {
+        return Err(NetworkError::PrefixOutOfRange(prefix));
+    }
+
+    Ok(prefix as u8)
+}
+
+impl Iterator for Ipv4Hosts {
+    type Item = Ipv4;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next >= self.end {
+            return None;
+        }
+
+        let host: Ipv4 = Handle::from(self.next as u32);
+        self.next += 1;
+
+        Some(host)
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        exact_size_hint(self.end.saturating_sub(self.next).into())
+    }
+}
+
+impl DoubleEndedIterator for Ipv4Hosts {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.next >= self.end {
+            return None;
+        }
+
+        self.end -= 1;
+
+        Some(Handle::from(self.end as u32))
+    }
+}
+
+impl Iterator for Ipv4Subnets {
+    type Item = Ipv4Network;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next >= self.end {
+            return None;
+        }
+
+        let subnet: Ipv4Network = Ipv4Network {
+            network: self.next as u32,
+            prefix: self.prefix,
+        };
+        self.next += 1 << (IPV4_BITS - self.prefix);
+
+        Some(subnet)
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        exact_size_hint((self.end.saturating_sub(self.next) >> (IPV4_BITS - self.prefix)).into())
+    }
+}
+
+impl Iterator for Ipv6Subnets {
+    type Item = Ipv6Network;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let network: u128 = self.next?;
+
+        self.next = match network < self.last {
+            true => Some(network + (1 << (IPV6_BITS - self.prefix))),
+            false => None,
+        };
+
+        Some(Ipv6Network {
+            network,
+            prefix: self.prefix,
+        })
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let Some(network) = self.next else {
+            return (0, Some(0));
+        };
+
+        // count of remaining subnets doesn't fit in u128 only for all /128 subnets of ::/0
+        match ((self.last - network).checked_shr((IPV6_BITS - self.prefix) as u32).unwrap_or(0)).checked_add(1) {
+            Some(len) => exact_size_hint(len),
+            None => (usize::MAX, None),
+        }
+    }
+}
+
+/// exact size hint which saturates when length doesn't fit in usize
+fn exact_size_hint(len: u128) -> (usize, Option<usize>) {
+    match usize::try_from(len) {
+        Ok(len) => (len, Some(len)),
+        Err(_) => (usize::MAX, None),
+    }
+}
+
+impl std::fmt::Debug for Ipv4Network {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(self, f)
+    }
+}
+
+impl std::fmt::Display for Ipv4Network {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.network(), self.prefix)
+    }
+}
+
+impl std::str::FromStr for Ipv4Network {
+    type Err = NetworkError;
+
+    /// parses network from `address/prefix` or `address/netmask` string
+    ///
+    /// # Example
+    /// ```
+    /// use curuam::*;
+    ///
+    /// let cidr: Ipv4Network = "10.0.0.0/8".parse().expect("invalid network");
+    /// let netmask: Ipv4Network = "10.0.0.0/255.0.0.0".parse().expect("invalid network");
+    ///
+    /// assert_eq!(cidr, netmask);
+    /// assert_eq!("10.0.0.0/33".parse::<Ipv4Network>().err(), Some(NetworkError::PrefixOutOfRange(33)));
+    /// assert_eq!("10.0.0.0/300".parse::<Ipv4Network>().err(), Some(NetworkError::PrefixOutOfRange(300)));
+    /// assert_eq!("10.0.0.0/08".parse::<Ipv4Network>().err(), Some(NetworkError::InvalidPrefix))
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (address, prefix): (&str, &str) = split_prefix(s)?;
+        let ip_addr: Ipv4 = address.parse().map_err(NetworkError::InvalidIpv4)?;
+
+        if prefix.contains('.') {
+            let netmask: Ipv4 = prefix.parse().map_err(NetworkError::InvalidIpv4)?;
+            return Self::with_netmask(ip_addr, netmask);
+        }
+
+        Self::new(ip_addr, parse_prefix(prefix, IPV4_BITS)?)
+    }
+}
+
+impl TryFrom<&str> for Ipv4Network {
+    type Error = NetworkError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl std::fmt::Debug for Ipv6Network {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(self, f)
+    }
+}
+
+impl std::fmt::Display for Ipv6Network {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.network(), self.prefix)
+    }
+}
+
+impl std::str::FromStr for Ipv6Network {
+    type Err = NetworkError;
+
+    /// parses network from `address/prefix` string
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (address, prefix): (&str, &str) = split_prefix(s)?;
+        let ip_addr: Ipv6 = address.parse().map_err(NetworkError::InvalidIpv6)?;
+
+        Self::new(ip_addr, parse_prefix(prefix, IPV6_BITS)?)
+    }
+}
+
+impl TryFrom<&str> for Ipv6Network {
+    type Error = NetworkError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl std::fmt::Display for NetworkError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            Self::MissingPrefix => write!(f, "missing `/` separated prefix"),
+            Self::InvalidPrefix => write!(f, "prefix is not a decimal number without leading zeros"),
+            Self::PrefixOutOfRange(prefix) => write!(f, "prefix {} is out of range", prefix),
+            Self::InvalidNetmask => write!(f, "netmask bits are not contiguous"),
+            Self::InvalidIpv4(err) => write!(f, "invalid ipv4 address: {}", err),
+            Self::InvalidIpv6(err) => write!(f, "invalid ipv6 address: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for NetworkError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InvalidIpv4(err) => Some(err),
+            Self::InvalidIpv6(err) => Some(err),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use std::io::Read;
+
+use crate::{Handle, Mac, MAC_LEN};
+
+mod csv;
+
+#[cfg(feature = "oui")]
+include!(concat!(env!("OUT_DIR"), "/oui_registry.rs"));
+
+/// prefix lengths of MA-S, MA-M and MA-L assignments, longest first
+const PREFIX_BITS: [u8; 3] = [36, 28, 24];
+
+/// vendor registry loaded at runtime from ieee csv files
+/// (`oui.csv`, `mam.csv` and `oui36.csv` from standards-oui.ieee.org)
+///
+/// # Example
+/// ```
+/// use curuam::*;
+///
+/// let registry = "Registry,Assignment,Organization Name,Organization Address\n\
+///     MA-L,AABBCC,\"Example, Inc\",Somewhere\n\
+///     MA-S,AABBCCDDE,Example Subsidiary,Somewhere\n";
+///
+/// let mut database = OuiDatabase::new();
+/// database.load_csv(registry.as_bytes()).expect("invalid registry");
+///
+/// let mac_addr: Mac = "aa:bb:cc:00:00:01".parse().expect("invalid mac");
+/// let sub_mac_addr: Mac = "aa:bb:cc:dd:ee:01".parse().expect("invalid mac");
+///
+/// assert_eq!(database.lookup(&mac_addr), Some("Example, Inc"));
+/// assert_eq!(database.lookup(&sub_mac_addr), Some("Example Subsidiary"))
+/// ```
+#[derive(Default)]
+pub struct OuiDatabase {
+    entries: Vec<(u64, u8, String)>,
+}
+
+impl OuiDatabase {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// creates database from a single registry file
+    pub fn from_csv<R: Read>(reader: R) -> std::io::Result<Self> {
+        let mut database: Self = Self::new();
+        database.load_csv(reader)?;
+
+        Ok(database)
+    }
+    /// adds entries of a registry file, entries already present are replaced
+    pub fn load_csv<R: Read>(&mut self, mut reader: R) -> std::io::Result<()> {
+        let mut text: String = String::new();
+        reader.read_to_string(&mut text)?;
+
+        let entries = csv::parse_registry(&text)
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))?;
+
+        // new entries go first so that they win the deduplication
+        let mut merged: Vec<(u64, u8, String)> = entries;
+        merged.append(&mut self.entries);
+        merged.sort_by_key(|entry| (entry.0, entry.1));
+        merged.dedup_by(|a, b| a.0 == b.0 && a.1 == b.1);
+
+        self.entries = merged;
+
+        Ok(())
+    }
+    /// returns organization name of the longest assignment containing the address
+    pub fn lookup(&self, mac_addr: &Mac) -> Option<&str> {
+        lookup(&self.entries, mac_addr).map(|vendor| vendor.as_str())
+    }
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(feature = "oui")]
+impl Mac {
+    /// returns vendor name from the embedded ieee registry, requires `oui` feature
+    ///
+    /// # Example
+    /// ```
+    /// use curuam::*;
+    ///
+    /// let mac_addr: Mac = "00:50:56:01:02:03".parse().expect("invalid mac");
+    /// let ma_s_addr: Mac = "8c:1f:64:af:a0:01".parse().expect("invalid mac");
+    ///
+    /// assert_eq!(mac_addr.vendor(), Some("VMware, Inc."));
+    /// assert_eq!(ma_s_addr.vendor(), Some("DATA ELECTRONIC DEVICES, INC"))
+    /// ```
+    pub fn vendor(&self) -> Option<&'static str> {
+        lookup(OUI_REGISTRY, self).map(|&index| OUI_ORGANIZATIONS[index as usize])
+    }
+}
+
+/// finds the longest prefix match in entries sorted by prefix and prefix length
+fn lookup<'a, T>(entries: &'a [(u64, u8, T)], mac_addr: &Mac) -> Option<&'a T> {
+    let octets: [u8; MAC_LEN] = Handle::to(mac_addr);
+    let mut bytes: [u8; 8] = [0; 8];
+    bytes[8 - MAC_LEN..].copy_from_slice(&octets);
+
+    let address: u64 = u64::from_be_bytes(bytes);
+
+    PREFIX_BITS.iter().find_map(|&bits| {
+        let key: (u64, u8) = (address & !((1 << (48 - bits)) - 1), bits);
+
+        entries
+            .binary_search_by(|entry| (entry.0, entry.1).cmp(&key))
+            .ok()
+            .map(|i| &entries[i].2)
+    })
+}
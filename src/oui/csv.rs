@@ -0,0 +1,81 @@
+/// registry entry as (left aligned 48-bit prefix, prefix length in bits, organization name)
+pub type RegistryEntry = (u64, u8, String);
+
+/// parses registry file in the ieee csv format
+/// (`Registry,Assignment,Organization Name,Organization Address`),
+/// entries of other registries than MA-L, MA-M and MA-S are skipped
+pub fn parse_registry(text: &str) -> Result<Vec<RegistryEntry>, String> {
+    let mut entries: Vec<RegistryEntry> = Vec::new();
+
+    for (line, record) in parse_records(text)?.into_iter().enumerate() {
+        if record.len() < 3 {
+            if record.iter().all(|field| field.trim().is_empty()) {
+                continue;
+            }
+            return Err(format!("record {} has {} fields, expected at least 3", line + 1, record.len()));
+        }
+
+        match record[0].trim() {
+            "MA-L" | "MA-M" | "MA-S" => {}
+            _ => continue,
+        }
+
+        let assignment: &str = record[1].trim();
+        let (prefix, bits): (u64, u8) = parse_assignment(assignment)
+            .ok_or_else(|| format!("record {} has invalid assignment {:?}", line + 1, assignment))?;
+
+        entries.push((prefix, bits, record[2].trim().to_string()));
+    }
+
+    Ok(entries)
+}
+
+/// parses hex assignment of MA-L, MA-M or MA-S into left aligned 48-bit prefix and its length in bits
+pub fn parse_assignment(assignment: &str) -> Option<(u64, u8)> {
+    let bits: u8 = match assignment.len() {
+        6 | 7 | 9 => 4 * assignment.len() as u8,
+        _ => return None,
+    };
+    let value: u64 = u64::from_str_radix(assignment, 16).ok()?;
+
+    Some((value << (48 - bits), bits))
+}
+
+/// splits csv text into records, quoted fields may contain separators, newlines and `""` escapes
+fn parse_records(text: &str) -> Result<Vec<Vec<String>>, String> {
+    let mut records: Vec<Vec<String>> = Vec::new();
+    let mut record: Vec<String> = Vec::new();
+    let mut field: String = String::new();
+    let mut quoted: bool = false;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (quoted, c) {
+            (true, '"') if chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            (true, '"') => quoted = false,
+            (true, c) => field.push(c),
+            (false, '"') => quoted = true,
+            (false, ',') => record.push(std::mem::take(&mut field)),
+            (false, '\r') => {}
+            (false, '\n') => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            (false, c) => field.push(c),
+        }
+    }
+
+    if quoted {
+        return Err("unterminated quoted field".to_string());
+    }
+
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+
+    Ok(records)
+}
@@ -0,0 +1,468 @@
+use std::io::{self, Read, Write};
+use std::time::Duration;
+
+use crate::Handle;
+
+/// length of pcap file header
+pub const PCAP_HEADER_SIZE: usize = 24;
+/// length of pcap record header
+pub const PCAP_RECORD_HEADER_SIZE: usize = 16;
+
+const MAGIC_MICROSECOND: u32 = 0xa1b2c3d4;
+const MAGIC_NANOSECOND: u32 = 0xa1b23c4d;
+/// captured length above this limit is treated as corrupted file regardless of snaplen,
+/// matches the largest snaplen used by libpcap
+const MAX_CAPTURED_LEN: u32 = 0x40000;
+
+/// link layer type of captured packets
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LinkType {
+    /// bsd loopback
+    Null,
+    Ethernet,
+    /// raw ipv4 or ipv6 packets
+    Raw,
+    Ieee80211,
+    /// linux cooked capture
+    LinuxSll,
+    Ipv4,
+    Ipv6,
+    Other(u32),
+}
+
+/// resolution of record timestamps
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PcapPrecision {
+    Microsecond,
+    Nanosecond,
+}
+
+/// global header of pcap file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PcapHeader {
+    pub version_major: u16,
+    pub version_minor: u16,
+    /// maximum captured length of a record
+    pub snaplen: u32,
+    pub link_type: LinkType,
+    pub precision: PcapPrecision,
+    /// byte order of the file
+    pub big_endian: bool,
+}
+
+/// captured packet
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PcapRecord {
+    /// time since unix epoch
+    pub timestamp: Duration,
+    /// length of the packet on the wire
+    pub original_len: u32,
+    /// captured bytes, may be shorter than the original packet
+    pub data: Vec<u8>,
+}
+
+/// streaming pcap reader
+///
+/// # Example
+/// ```
+/// use curuam::*;
+/// use std::time::Duration;
+///
+/// let mac_addr: Mac = "00:11:22:33:44:55".parse().expect("invalid mac");
+/// let ip: Ipv4 = "192.168.1.10".parse().expect("invalid ip");
+///
+/// let mut writer = PcapWriter::new(Vec::new(), LinkType::Ethernet).expect("failed to write header");
+/// writer
+///     .write_packet(Duration::from_secs(1), &ArpHeader::gratuitous(mac_addr, ip))
+///     .expect("failed to write packet");
+/// let file: Vec<u8> = writer.into_inner();
+///
+/// let mut reader = PcapReader::new(file.as_slice()).expect("invalid pcap");
+/// assert_eq!(reader.header().link_type, LinkType::Ethernet);
+///
+/// let record: PcapRecord = reader.next().expect("no records").expect("invalid record");
+/// let (eth, payload) = EthHeader::parse(&record.data).expect("invalid frame");
+/// let (arp, _) = ArpHeader::parse(payload).expect("invalid arp");
+///
+/// assert_eq!(record.timestamp, Duration::from_secs(1));
+/// assert_eq!(eth.source(), mac_addr);
+/// assert!(arp.is_gratuitous());
+/// assert!(reader.next().is_none())
+/// ```
+#[derive(Debug)]
+pub struct PcapReader<R: Read> {
+    reader: R,
+    header: PcapHeader,
+    /// set after the first error so that iteration stops
+    failed: bool,
+}
+
+/// streaming pcap writer, records longer than snaplen (at most 262144 bytes) are truncated
+#[derive(Debug)]
+pub struct PcapWriter<W: Write> {
+    writer: W,
+    header: PcapHeader,
+}
+
+/// error returned when reading or writing pcap files
+#[derive(Debug)]
+pub enum PcapError {
+    Io(io::Error),
+    /// file doesn't start with a known pcap magic number
+    InvalidMagic(u32),
+    /// record is longer than the file allows
+    InvalidCapturedLength(u32),
+    /// file format version other than 2.x
+    UnsupportedVersion { major: u16, minor: u16 },
+    /// timestamp seconds don't fit in 32 bits, it's after february 2106
+    InvalidTimestamp(u64),
+}
+
+impl PcapHeader {
+    /// creates little endian header with microsecond timestamps and 65535 bytes snaplen
+    pub fn new(link_type: LinkType) -> Self {
+        Self {
+            version_major: 2,
+            version_minor: 4,
+            snaplen: 65535,
+            link_type,
+            precision: PcapPrecision::Microsecond,
+            big_endian: false,
+        }
+    }
+    /// parses file header, only version 2.x is supported
+    ///
+    /// # Example
+    /// ```
+    /// use curuam::*;
+    ///
+    /// let mut header: PcapHeader = PcapHeader::new(LinkType::Ethernet);
+    /// assert_eq!(PcapHeader::from_bytes(header.to_bytes()).expect("invalid header"), header);
+    ///
+    /// header.version_major = 3;
+    /// assert!(matches!(
+    ///     PcapHeader::from_bytes(header.to_bytes()),
+    ///     Err(PcapError::UnsupportedVersion { major: 3, minor: 4 })
+    /// ))
+    /// ```
+    pub fn from_bytes(bytes: [u8; PCAP_HEADER_SIZE]) -> Result<Self, PcapError> {
+        let magic: [u8; 4] = [bytes[0], bytes[1], bytes[2], bytes[3]];
+
+        let (precision, big_endian): (PcapPrecision, bool) = match u32::from_le_bytes(magic) {
+            MAGIC_MICROSECOND => (PcapPrecision::Microsecond, false),
+            MAGIC_NANOSECOND => (PcapPrecision::Nanosecond, false),
+            magic if magic.swap_bytes() == MAGIC_MICROSECOND => (PcapPrecision::Microsecond, true),
+            magic if magic.swap_bytes() == MAGIC_NANOSECOND => (PcapPrecision::Nanosecond, true),
+            _ => return Err(PcapError::InvalidMagic(u32::from_be_bytes(magic))),
+        };
+
+        let u16_at = |offset: usize| -> u16 { read_u16([bytes[offset], bytes[offset + 1]], big_endian) };
+        let u32_at = |offset: usize| -> u32 {
+            read_u32([bytes[offset], bytes[offset + 1], bytes[offset + 2], bytes[offset + 3]], big_endian)
+        };
+
+        let (version_major, version_minor): (u16, u16) = (u16_at(4), u16_at(6));
+        if version_major != 2 {
+            return Err(PcapError::UnsupportedVersion {
+                major: version_major,
+                minor: version_minor,
+            });
+        }
+
+        Ok(Self {
+            version_major,
+            version_minor,
+            snaplen: u32_at(16),
+            // upper bits hold fcs length and reserved flags
+            link_type: Handle::from(u32_at(20) & 0x0fff_ffff),
+            precision,
+            big_endian,
+        })
+    }
+    pub fn to_bytes(&self) -> [u8; PCAP_HEADER_SIZE] {
+        let magic: u32 = match self.precision {
+            PcapPrecision::Microsecond => MAGIC_MICROSECOND,
+            PcapPrecision::Nanosecond => MAGIC_NANOSECOND,
+        };
+
+        let mut bytes: [u8; PCAP_HEADER_SIZE] = [0; PCAP_HEADER_SIZE];
+        bytes[0..4].copy_from_slice(&write_u32(magic, self.big_endian));
+        bytes[4..6].copy_from_slice(&write_u16(self.version_major, self.big_endian));
+        bytes[6..8].copy_from_slice(&write_u16(self.version_minor, self.big_endian));
+        bytes[16..20].copy_from_slice(&write_u32(self.snaplen, self.big_endian));
+        bytes[20..24].copy_from_slice(&write_u32(self.link_type.to(), self.big_endian));
+
+        bytes
+    }
+}
+
+impl PcapRecord {
+    /// length of captured bytes
+    pub fn captured_len(&self) -> usize {
+        self.data.len()
+    }
+    /// checks whether packet was cut by snaplen
+    pub fn is_truncated(&self) -> bool {
+        self.data.len() < self.original_len as usize
+    }
+}
+
+impl<R: Read> PcapReader<R> {
+    /// reads file header
+    pub fn new(mut reader: R) -> Result<Self, PcapError> {
+        let mut bytes: [u8; PCAP_HEADER_SIZE] = [0; PCAP_HEADER_SIZE];
+        reader.read_exact(&mut bytes)?;
+
+        Ok(Self {
+            reader,
+            header: PcapHeader::from_bytes(bytes)?,
+            failed: false,
+        })
+    }
+    pub fn header(&self) -> &PcapHeader {
+        &self.header
+    }
+    /// reads next record, returns `None` at the end of file
+    ///
+    /// # Example
+    /// ```
+    /// use curuam::*;
+    ///
+    /// let mut header: PcapHeader = PcapHeader::new(LinkType::Ethernet);
+    /// header.snaplen = u32::MAX;
+    ///
+    /// let mut file: Vec<u8> = header.to_bytes().to_vec();
+    /// file.extend_from_slice(&[0; 8]);
+    /// file.extend_from_slice(&[0xff; 8]);
+    ///
+    /// let mut reader = PcapReader::new(file.as_slice()).expect("invalid pcap");
+    ///
+    /// assert!(matches!(reader.next(), Some(Err(PcapError::InvalidCapturedLength(u32::MAX)))));
+    /// assert!(reader.next().is_none());
+    ///
+    /// // record can't be longer than snaplen of the file
+    /// header.snaplen = 64;
+    ///
+    /// let mut file: Vec<u8> = header.to_bytes().to_vec();
+    /// file.extend_from_slice(&[0; 8]);
+    /// file.extend_from_slice(&65u32.to_le_bytes());
+    /// file.extend_from_slice(&65u32.to_le_bytes());
+    ///
+    /// let mut reader = PcapReader::new(file.as_slice()).expect("invalid pcap");
+    ///
+    /// assert!(matches!(reader.next(), Some(Err(PcapError::InvalidCapturedLength(65)))))
+    /// ```
+    pub fn read_record(&mut self) -> Result<Option<PcapRecord>, PcapError> {
+        let mut bytes: [u8; PCAP_RECORD_HEADER_SIZE] = [0; PCAP_RECORD_HEADER_SIZE];
+
+        // end of file is only valid on record boundary
+        let mut read: usize = 0;
+        while read < PCAP_RECORD_HEADER_SIZE {
+            match self.reader.read(&mut bytes[read..]) {
+                Ok(0) if read == 0 => return Ok(None),
+                Ok(0) => return Err(PcapError::Io(io::ErrorKind::UnexpectedEof.into())),
+                Ok(len) => read += len,
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+                Err(error) => return Err(PcapError::Io(error)),
+            }
+        }
+
+        let big_endian: bool = self.header.big_endian;
+        let u32_at = |offset: usize| -> u32 {
+            read_u32([bytes[offset], bytes[offset + 1], bytes[offset + 2], bytes[offset + 3]], big_endian)
+        };
+
+        let (seconds, fraction, captured_len, original_len): (u32, u32, u32, u32) =
+            (u32_at(0), u32_at(4), u32_at(8), u32_at(12));
+
+        // some writers leave snaplen zero, only the hard limit applies to them
+        let max_len: u32 = match self.header.snaplen {
+            0 => MAX_CAPTURED_LEN,
+            snaplen => snaplen.min(MAX_CAPTURED_LEN),
+        };
+        if captured_len > max_len {
+            return Err(PcapError::InvalidCapturedLength(captured_len));
+        }
+
+        let mut data: Vec<u8> = vec![0; captured_len as usize];
+        self.reader.read_exact(&mut data)?;
+
+        let nanos: u32 = match self.header.precision {
+            PcapPrecision::Microsecond => fraction.saturating_mul(1_000),
+            PcapPrecision::Nanosecond => fraction,
+        };
+
+        Ok(Some(PcapRecord {
+            timestamp: Duration::from_secs(seconds as u64) + Duration::from_nanos(nanos as u64),
+            original_len,
+            data,
+        }))
+    }
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R: Read> Iterator for PcapReader<R> {
+    type Item = Result<PcapRecord, PcapError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+
+        let record: Option<Self::Item> = self.read_record().transpose();
+        self.failed = matches!(record, Some(Err(_)));
+
+        record
+    }
+}
+
+impl<W: Write> PcapWriter<W> {
+    /// writes default header for the link type
+    pub fn new(writer: W, link_type: LinkType) -> Result<Self, PcapError> {
+        Self::with_header(writer, PcapHeader::new(link_type))
+    }
+    /// writes the given header
+    pub fn with_header(mut writer: W, header: PcapHeader) -> Result<Self, PcapError> {
+        writer.write_all(&header.to_bytes())?;
+
+        Ok(Self { writer, header })
+    }
+    pub fn header(&self) -> &PcapHeader {
+        &self.header
+    }
+    /// writes packet captured at `timestamp` since unix epoch, which has to be before 2106
+    ///
+    /// # Example
+    /// ```
+    /// use curuam::*;
+    /// use std::time::Duration;
+    ///
+    /// let mut writer = PcapWriter::new(Vec::new(), LinkType::Ethernet).expect("can't write header");
+    /// let timestamp: Duration = Duration::from_secs(u32::MAX as u64 + 1);
+    ///
+    /// assert!(writer.write_packet(Duration::from_secs(1_700_000_000), &[0; 60]).is_ok());
+    /// assert!(matches!(
+    ///     writer.write_packet(timestamp, &[0; 60]),
+    ///     Err(PcapError::InvalidTimestamp(seconds)) if seconds == timestamp.as_secs()
+    /// ))
+    /// ```
+    pub fn write_packet(&mut self, timestamp: Duration, packet: &[u8]) -> Result<(), PcapError> {
+        self.write_captured(timestamp, u32::try_from(packet.len()).unwrap_or(u32::MAX), packet)
+    }
+    /// writes record keeping its original length
+    pub fn write_record(&mut self, record: &PcapRecord) -> Result<(), PcapError> {
+        self.write_captured(record.timestamp, record.original_len, &record.data)
+    }
+    pub fn flush(&mut self) -> Result<(), PcapError> {
+        Ok(self.writer.flush()?)
+    }
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+    fn write_captured(&mut self, timestamp: Duration, original_len: u32, data: &[u8]) -> Result<(), PcapError> {
+        let seconds: u32 =
+            u32::try_from(timestamp.as_secs()).map_err(|_| PcapError::InvalidTimestamp(timestamp.as_secs()))?;
+        let data: &[u8] = &data[..data.len().min(self.header.snaplen.min(MAX_CAPTURED_LEN) as usize)];
+        let big_endian: bool = self.header.big_endian;
+
+        let fraction: u32 = match self.header.precision {
+            PcapPrecision::Microsecond => timestamp.subsec_micros(),
+            PcapPrecision::Nanosecond => timestamp.subsec_nanos(),
+        };
+
+        let mut bytes: [u8; PCAP_RECORD_HEADER_SIZE] = [0; PCAP_RECORD_HEADER_SIZE];
+        bytes[0..4].copy_from_slice(&write_u32(seconds, big_endian));
+        bytes[4..8].copy_from_slice(&write_u32(fraction, big_endian));
+        bytes[8..12].copy_from_slice(&write_u32(data.len() as u32, big_endian));
+        bytes[12..16].copy_from_slice(&write_u32(original_len.max(data.len() as u32), big_endian));
+
+        self.writer.write_all(&bytes)?;
+        self.writer.write_all(data)?;
+
+        Ok(())
+    }
+}
+
+fn read_u16(bytes: [u8; 2], big_endian: bool) -> u16 {
+    match big_endian {
+        true => u16::from_be_bytes(bytes),
+        false => u16::from_le_bytes(bytes),
+    }
+}
+
+fn read_u32(bytes: [u8; 4], big_endian: bool) -> u32 {
+    match big_endian {
+        true => u32::from_be_bytes(bytes),
+        false => u32::from_le_bytes(bytes),
+    }
+}
+
+fn write_u16(value: u16, big_endian: bool) -> [u8; 2] {
+    match big_endian {
+        true => value.to_be_bytes(),
+        false => value.to_le_bytes(),
+    }
+}
+
+fn write_u32(value: u32, big_endian: bool) -> [u8; 4] {
+    match big_endian {
+        true => value.to_be_bytes(),
+        false => value.to_le_bytes(),
+    }
+}
+
+impl Handle<u32> for LinkType {
+    fn from(value: u32) -> Self {
+        match value {
+            0 => Self::Null,
+            1 => Self::Ethernet,
+            101 => Self::Raw,
+            105 => Self::Ieee80211,
+            113 => Self::LinuxSll,
+            228 => Self::Ipv4,
+            229 => Self::Ipv6,
+            value => Self::Other(value),
+        }
+    }
+    fn to(&self) -> u32 {
+        match *self {
+            Self::Null => 0,
+            Self::Ethernet => 1,
+            Self::Raw => 101,
+            Self::Ieee80211 => 105,
+            Self::LinuxSll => 113,
+            Self::Ipv4 => 228,
+            Self::Ipv6 => 229,
+            Self::Other(value) => value,
+        }
+    }
+}
+
+impl From<io::Error> for PcapError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl std::fmt::Display for PcapError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "io error: {}", error),
+            Self::InvalidMagic(magic) => write!(f, "invalid pcap magic {:#010x}", magic),
+            Self::InvalidCapturedLength(len) => write!(f, "invalid captured length {}", len),
+            Self::UnsupportedVersion { major, minor } => write!(f, "unsupported pcap version {}.{}", major, minor),
+            Self::InvalidTimestamp(seconds) => write!(f, "timestamp {} seconds doesn't fit in pcap record", seconds),
+        }
+    }
+}
+
+impl std::error::Error for PcapError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
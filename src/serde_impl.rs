@@ -0,0 +1,209 @@
+//! serde support, addresses are serialized as canonical strings for human-readable formats
+//! and as byte strings of raw octets (4 for ipv4, 16 for ipv6, 6 for mac) for binary formats
+//!
+//! # Example
+//! ```
+//! use curuam::*;
+//!
+//! let ip_addr: Ipv6 = "2001:db8::1".parse().expect("invalid ip");
+//! let mac_addr: Mac = "00:11:22:33:44:55".parse().expect("invalid mac");
+//!
+//! assert_eq!(serde_json::to_string(&ip_addr).expect("serialize"), "\"2001:db8::1\"");
+//! assert_eq!(serde_json::to_string(&mac_addr).expect("serialize"), "\"00:11:22:33:44:55\"");
+//! assert_eq!(serde_json::from_str::<Ipv6>("\"2001:db8::1\"").expect("deserialize"), ip_addr)
+//! ```
+//!
+//! # Binary Example
+//! ```
+//! use curuam::*;
+//!
+//! let ipv4_addr: Ipv4 = "192.168.1.1".parse().expect("invalid ip");
+//! let ipv6_addr: Ipv6 = "2001:db8::1".parse().expect("invalid ip");
+//! let mac_addr: Mac = "00:11:22:33:44:55".parse().expect("invalid mac");
+//! let ip_addr: IpAddress = "2001:db8::1".parse().expect("invalid ip");
+//!
+//! // bincode prefixes byte strings with u64 length
+//! let bytes: Vec<u8> = bincode::serialize(&ipv4_addr).expect("serialize");
+//! assert_eq!(bytes, [4, 0, 0, 0, 0, 0, 0, 0, 192, 168, 1, 1]);
+//! assert_eq!(bincode::deserialize::<Ipv4>(&bytes).expect("deserialize"), ipv4_addr);
+//!
+//! let bytes: Vec<u8> = bincode::serialize(&ipv6_addr).expect("serialize");
+//! assert_eq!(bytes.len(), 8 + 16);
+//! assert_eq!(bincode::deserialize::<Ipv6>(&bytes).expect("deserialize"), ipv6_addr);
+//!
+//! let bytes: Vec<u8> = bincode::serialize(&mac_addr).expect("serialize");
+//! assert_eq!(bytes, [6, 0, 0, 0, 0, 0, 0, 0, 0x00, 0x11, 0x22, 0x33, 0x44, 0x55]);
+//! assert_eq!(bincode::deserialize::<Mac>(&bytes).expect("deserialize"), mac_addr);
+//!
+//! // ip address uses the same encoding as the address it holds
+//! let bytes: Vec<u8> = bincode::serialize(&ip_addr).expect("serialize");
+//! assert_eq!(bytes, bincode::serialize(&ipv6_addr).expect("serialize"));
+//! assert_eq!(bincode::deserialize::<IpAddress>(&bytes).expect("deserialize"), ip_addr);
+//!
+//! let ip_addr: IpAddress = IpAddress::V4(ipv4_addr);
+//! let bytes: Vec<u8> = bincode::serialize(&ip_addr).expect("serialize");
+//! assert_eq!(bincode::deserialize::<IpAddress>(&bytes).expect("deserialize"), ip_addr);
+//!
+//! // wrong number of octets is rejected
+//! assert!(bincode::deserialize::<Ipv4>(&bincode::serialize(&mac_addr).expect("serialize")).is_err());
+//! ```
+
+use std::{fmt, marker::PhantomData, str::FromStr};
+
+use serde::{
+    de::{self, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::{Handle, IpAddress, Ipv4, Ipv4Network, Ipv6, Ipv6Network, Mac, IPV4_LEN, IPV6_LEN, MAC_LEN};
+
+macro_rules! impl_serde {
+    ($type:ty, $len:expr, $expecting:literal) => {
+        impl Serialize for $type {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                if serializer.is_human_readable() {
+                    serializer.collect_str(self)
+                } else {
+                    serializer.serialize_bytes(&Handle::<[u8; $len]>::to(self))
+                }
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $type {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                if deserializer.is_human_readable() {
+                    deserializer.deserialize_str(FromStrVisitor::<Self>::new($expecting))
+                } else {
+                    deserializer.deserialize_bytes(OctetsVisitor::<Self, $len>::new($expecting))
+                }
+            }
+        }
+    };
+}
+
+impl_serde!(Ipv4, IPV4_LEN, "ipv4 address");
+impl_serde!(Ipv6, IPV6_LEN, "ipv6 address");
+impl_serde!(Mac, MAC_LEN, "mac address");
+
+impl Serialize for IpAddress {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            return serializer.collect_str(self);
+        }
+
+        match self {
+            Self::V4(ip_addr) => serializer.serialize_bytes(&Handle::<[u8; IPV4_LEN]>::to(ip_addr)),
+            Self::V6(ip_addr) => serializer.serialize_bytes(&Handle::<[u8; IPV6_LEN]>::to(ip_addr)),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for IpAddress {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            return deserializer.deserialize_str(FromStrVisitor::<Self>::new("ip address"));
+        }
+
+        deserializer.deserialize_bytes(IpAddressBytesVisitor)
+    }
+}
+
+impl Serialize for Ipv4Network {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Ipv4Network {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(FromStrVisitor::<Self>::new("ipv4 network"))
+    }
+}
+
+impl Serialize for Ipv6Network {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Ipv6Network {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(FromStrVisitor::<Self>::new("ipv6 network"))
+    }
+}
+
+struct FromStrVisitor<T> {
+    expecting: &'static str,
+    marker: PhantomData<T>,
+}
+
+impl<T> FromStrVisitor<T> {
+    fn new(expecting: &'static str) -> Self {
+        Self {
+            expecting,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Visitor<'_> for FromStrVisitor<T>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.expecting)
+    }
+    fn visit_str<E: de::Error>(self, s: &str) -> Result<Self::Value, E> {
+        s.parse().map_err(E::custom)
+    }
+}
+
+struct OctetsVisitor<T, const N: usize> {
+    expecting: &'static str,
+    marker: PhantomData<T>,
+}
+
+impl<T, const N: usize> OctetsVisitor<T, N> {
+    fn new(expecting: &'static str) -> Self {
+        Self {
+            expecting,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T: Handle<[u8; N]>, const N: usize> Visitor<'_> for OctetsVisitor<T, N> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} octets of {}", N, self.expecting)
+    }
+    fn visit_bytes<E: de::Error>(self, bytes: &[u8]) -> Result<Self::Value, E> {
+        <[u8; N]>::try_from(bytes)
+            .map(T::from)
+            .map_err(|_| E::invalid_length(bytes.len(), &self))
+    }
+}
+
+struct IpAddressBytesVisitor;
+
+impl Visitor<'_> for IpAddressBytesVisitor {
+    type Value = IpAddress;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("4 or 16 octets of ip address")
+    }
+    fn visit_bytes<E: de::Error>(self, bytes: &[u8]) -> Result<Self::Value, E> {
+        if let Ok(octets) = <[u8; IPV4_LEN]>::try_from(bytes) {
+            return Ok(IpAddress::V4(Handle::from(octets)));
+        }
+        if let Ok(octets) = <[u8; IPV6_LEN]>::try_from(bytes) {
+            return Ok(IpAddress::V6(Handle::from(octets)));
+        }
+
+        Err(E::invalid_length(bytes.len(), &self))
+    }
+}
@@ -0,0 +1,233 @@
+use crate::{u32_at, FromBytes, ParseError, TcpHeader, TCP_HEADER_SIZE};
+
+const FIN: u8 = 0x01;
+const SYN: u8 = 0x02;
+const RST: u8 = 0x04;
+const PSH: u8 = 0x08;
+const ACK: u8 = 0x10;
+const URG: u8 = 0x20;
+const ECE: u8 = 0x40;
+const CWR: u8 = 0x80;
+
+/// tcp option
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TcpOption<'a> {
+    EndOfList,
+    NoOperation,
+    MaximumSegmentSize(u16),
+    WindowScale(u8),
+    SackPermitted,
+    /// selective acknowledgment blocks as (left edge, right edge)
+    Sack(Vec<(u32, u32)>),
+    Timestamps { value: u32, echo_reply: u32 },
+    Unknown { kind: u8, data: &'a [u8] },
+}
+
+/// iterator over options of tcp header, stops after end of list option
+#[derive(Debug, Clone)]
+pub struct TcpOptions<'a> {
+    bytes: &'a [u8],
+}
+
+impl TcpHeader {
+    /// splits segment into header, options and payload
+    ///
+    /// # Example
+    /// ```
+    /// use curuam::*;
+    ///
+    /// let segment: [u8; 26] = [
+    ///     0x30, 0x39, 0x00, 0x50, // ports
+    ///     0x00, 0x00, 0x00, 0x01, // sequence number
+    ///     0x00, 0x00, 0x00, 0x00, // acknowledgment number
+    ///     0x60, 0x02, 0xff, 0xff, // data offset, flags, window
+    ///     0x00, 0x00, 0x00, 0x00, // checksum, urgent pointer
+    ///     0x02, 0x04, 0x05, 0xb4, // mss option
+    ///     0x01, 0x00, // payload
+    /// ];
+    ///
+    /// let (header, options, payload) = TcpHeader::parse(&segment).expect("invalid segment");
+    /// let options: Vec<TcpOption> = options.collect::<Result<_, _>>().expect("invalid option");
+    ///
+    /// assert_eq!(header.source_port(), 12345);
+    /// assert_eq!(header.destination_port(), 80);
+    /// assert!(header.syn() && !header.ack());
+    /// assert_eq!(header.header_len_bytes(), 24);
+    /// assert_eq!(options[0], TcpOption::MaximumSegmentSize(1460));
+    /// assert_eq!(payload, [0x01, 0x00])
+    /// ```
+    pub fn parse(segment: &[u8]) -> Result<(&TcpHeader, TcpOptions<'_>, &[u8]), ParseError> {
+        let (header, _): (&TcpHeader, &[u8]) = TcpHeader::ref_from(segment)?;
+
+        let header_len: usize = header.header_len_bytes();
+        if header_len < TCP_HEADER_SIZE {
+            return Err(ParseError::InvalidHeaderLength(header_len));
+        }
+        if header_len > segment.len() {
+            return Err(ParseError::Truncated {
+                needed: header_len,
+                available: segment.len(),
+            });
+        }
+
+        let options: TcpOptions = TcpOptions::new(&segment[TCP_HEADER_SIZE..header_len]);
+
+        Ok((header, options, &segment[header_len..]))
+    }
+    pub fn source_port(&self) -> u16 {
+        self.source.get()
+    }
+    pub fn set_source_port(&mut self, port: u16) {
+        self.source.set(port)
+    }
+    pub fn destination_port(&self) -> u16 {
+        self.dest.get()
+    }
+    pub fn set_destination_port(&mut self, port: u16) {
+        self.dest.set(port)
+    }
+    pub fn sequence(&self) -> u32 {
+        self.seq.get()
+    }
+    pub fn set_sequence(&mut self, sequence: u32) {
+        self.seq.set(sequence)
+    }
+    pub fn acknowledgment(&self) -> u32 {
+        self.ack_seq.get()
+    }
+    pub fn set_acknowledgment(&mut self, acknowledgment: u32) {
+        self.ack_seq.set(acknowledgment)
+    }
+    /// header length in 32-bit words
+    pub fn data_offset(&self) -> u8 {
+        self.doff >> 4
+    }
+    pub fn set_data_offset(&mut self, data_offset: u8) {
+        self.doff = (data_offset << 4) | (self.doff & 0x0f)
+    }
+    pub fn header_len_bytes(&self) -> usize {
+        4 * self.data_offset() as usize
+    }
+    pub fn window(&self) -> u16 {
+        self.window.get()
+    }
+    pub fn set_window(&mut self, window: u16) {
+        self.window.set(window)
+    }
+    pub fn urgent_pointer(&self) -> u16 {
+        self.urg_ptr.get()
+    }
+    pub fn set_urgent_pointer(&mut self, urgent_pointer: u16) {
+        self.urg_ptr.set(urgent_pointer)
+    }
+    pub fn fin(&self) -> bool {
+        self.flags & FIN != 0
+    }
+    pub fn set_fin(&mut self, value: bool) {
+        self.set_flag(FIN, value)
+    }
+    pub fn syn(&self) -> bool {
+        self.flags & SYN != 0
+    }
+    pub fn set_syn(&mut self, value: bool) {
+        self.set_flag(SYN, value)
+    }
+    pub fn rst(&self) -> bool {
+        self.flags & RST != 0
+    }
+    pub fn set_rst(&mut self, value: bool) {
+        self.set_flag(RST, value)
+    }
+    pub fn psh(&self) -> bool {
+        self.flags & PSH != 0
+    }
+    pub fn set_psh(&mut self, value: bool) {
+        self.set_flag(PSH, value)
+    }
+    pub fn ack(&self) -> bool {
+        self.flags & ACK != 0
+    }
+    pub fn set_ack(&mut self, value: bool) {
+        self.set_flag(ACK, value)
+    }
+    pub fn urg(&self) -> bool {
+        self.flags & URG != 0
+    }
+    pub fn set_urg(&mut self, value: bool) {
+        self.set_flag(URG, value)
+    }
+    pub fn ece(&self) -> bool {
+        self.flags & ECE != 0
+    }
+    pub fn set_ece(&mut self, value: bool) {
+        self.set_flag(ECE, value)
+    }
+    pub fn cwr(&self) -> bool {
+        self.flags & CWR != 0
+    }
+    pub fn set_cwr(&mut self, value: bool) {
+        self.set_flag(CWR, value)
+    }
+    fn set_flag(&mut self, flag: u8, value: bool) {
+        match value {
+            true => self.flags |= flag,
+            false => self.flags &= !flag,
+        }
+    }
+}
+
+impl<'a> TcpOptions<'a> {
+    /// creates iterator over raw options bytes
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+}
+
+impl<'a> Iterator for TcpOptions<'a> {
+    type Item = Result<TcpOption<'a>, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (&kind, rest): (&u8, &[u8]) = self.bytes.split_first()?;
+
+        match kind {
+            0 => {
+                self.bytes = &[];
+                return Some(Ok(TcpOption::EndOfList));
+            }
+            1 => {
+                self.bytes = rest;
+                return Some(Ok(TcpOption::NoOperation));
+            }
+            _ => {}
+        }
+
+        let len: usize = match rest.first() {
+            Some(&len) if len >= 2 && len as usize <= self.bytes.len() => len as usize,
+            _ => {
+                self.bytes = &[];
+                return Some(Err(ParseError::InvalidOption(kind)));
+            }
+        };
+
+        let data: &'a [u8] = &self.bytes[2..len];
+        self.bytes = &self.bytes[len..];
+
+        Some(match (kind, data) {
+            (2, [high, low]) => Ok(TcpOption::MaximumSegmentSize(u16::from_be_bytes([*high, *low]))),
+            (3, [shift]) => Ok(TcpOption::WindowScale(*shift)),
+            (4, []) => Ok(TcpOption::SackPermitted),
+            (5, blocks) if !blocks.is_empty() && blocks.len() % 8 == 0 => Ok(TcpOption::Sack(
+                blocks
+                    .chunks_exact(8)
+                    .map(|block| (u32_at(block, 0), u32_at(block, 4)))
+                    .collect(),
+            )),
+            (8, timestamps) if timestamps.len() == 8 => Ok(TcpOption::Timestamps {
+                value: u32_at(timestamps, 0),
+                echo_reply: u32_at(timestamps, 4),
+            }),
+            (2..=5, _) | (8, _) => Err(ParseError::InvalidOption(kind)),
+            (kind, data) => Ok(TcpOption::Unknown { kind, data }),
+        })
+    }
+}
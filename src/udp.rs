@@ -0,0 +1,252 @@
+use crate::{
+    BuildError, Checksum, FromBytes, IpProtocol, Ipv4, Ipv4Header, Ipv6, Ipv6Header, ParseError, UdpHeader, U16Be,
+    UDP_HEADER_SIZE,
+};
+
+const CHECKSUM_OFFSET: usize = 6;
+
+/// builder of udp datagrams which fills length and checksum, checksum always covers
+/// pseudo header of the address family the builder was created for
+///
+/// # Example
+/// ```
+/// use curuam::*;
+///
+/// let source: Ipv4 = "10.0.0.1".parse().expect("invalid ip");
+/// let destination: Ipv4 = "10.0.0.2".parse().expect("invalid ip");
+///
+/// let datagram: Vec<u8> = UdpHeader::builder_ipv4(5353, 53, source, destination)
+///     .payload(b"query")
+///     .build()
+///     .expect("datagram is too large");
+///
+/// let packet: Vec<u8> = Ipv4Header::builder(source, destination, IpProtocol::Udp)
+///     .payload(&datagram)
+///     .build()
+///     .expect("packet is too large");
+///
+/// let (ip_header, ip_payload) = Ipv4Header::parse(&packet).expect("invalid packet");
+/// let (udp_header, payload) = UdpHeader::parse(ip_payload).expect("invalid datagram");
+///
+/// assert_eq!(udp_header.destination_port(), 53);
+/// assert_eq!(udp_header.length(), 13);
+/// assert!(UdpHeader::verify_ipv4(ip_header, ip_payload));
+/// assert_eq!(payload, b"query")
+/// ```
+#[derive(Debug, Clone)]
+pub struct UdpHeaderBuilder<'a> {
+    source_port: u16,
+    destination_port: u16,
+    pseudo_header: PseudoHeader,
+    payload: &'a [u8],
+}
+
+#[derive(Debug, Clone, Copy)]
+enum PseudoHeader {
+    Ipv4(Ipv4, Ipv4),
+    Ipv6(Ipv6, Ipv6),
+}
+
+impl UdpHeader {
+    /// creates builder of datagram carried over ipv4
+    pub fn builder_ipv4<'a>(
+        source_port: u16,
+        destination_port: u16,
+        source: Ipv4,
+        destination: Ipv4,
+    ) -> UdpHeaderBuilder<'a> {
+        UdpHeaderBuilder::ipv4(source_port, destination_port, source, destination)
+    }
+    /// creates builder of datagram carried over ipv6, where checksum is mandatory
+    ///
+    /// # Example
+    /// ```
+    /// use curuam::*;
+    ///
+    /// let source: Ipv6 = "2001:db8::1".parse().expect("invalid ip");
+    /// let destination: Ipv6 = "2001:db8::2".parse().expect("invalid ip");
+    ///
+    /// let datagram: Vec<u8> = UdpHeader::builder_ipv6(5353, 53, source, destination)
+    ///     .payload(b"query")
+    ///     .build()
+    ///     .expect("datagram is too large");
+    ///
+    /// let (udp_header, _) = UdpHeader::parse(&datagram).expect("invalid datagram");
+    /// let check: u16 = Checksum::new()
+    ///     .add_ipv6_pseudo_header(&source, &destination, IpProtocol::Udp, datagram.len() as u32)
+    ///     .add_bytes(&datagram)
+    ///     .finish();
+    ///
+    /// assert_ne!(udp_header.checksum(), 0);
+    /// assert_eq!(check, 0)
+    /// ```
+    pub fn builder_ipv6<'a>(
+        source_port: u16,
+        destination_port: u16,
+        source: Ipv6,
+        destination: Ipv6,
+    ) -> UdpHeaderBuilder<'a> {
+        UdpHeaderBuilder::ipv6(source_port, destination_port, source, destination)
+    }
+    /// splits datagram into header and payload, payload is trimmed to the length field
+    pub fn parse(datagram: &[u8]) -> Result<(&UdpHeader, &[u8]), ParseError> {
+        let (header, _): (&UdpHeader, &[u8]) = UdpHeader::ref_from(datagram)?;
+
+        let len: usize = header.length() as usize;
+        if len < UDP_HEADER_SIZE || len > datagram.len() {
+            return Err(ParseError::InvalidTotalLength(len));
+        }
+
+        Ok((header, &datagram[UDP_HEADER_SIZE..len]))
+    }
+    pub fn source_port(&self) -> u16 {
+        self.source.get()
+    }
+    pub fn set_source_port(&mut self, port: u16) {
+        self.source.set(port)
+    }
+    pub fn destination_port(&self) -> u16 {
+        self.dest.get()
+    }
+    pub fn set_destination_port(&mut self, port: u16) {
+        self.dest.set(port)
+    }
+    /// length of header and payload
+    pub fn length(&self) -> u16 {
+        self.len.get()
+    }
+    pub fn checksum(&self) -> u16 {
+        self.check.get()
+    }
+    /// computes checksum of datagram with ipv4 pseudo header, current checksum field is ignored
+    pub fn compute_checksum_ipv4(ip_header: &Ipv4Header, datagram: &[u8]) -> u16 {
+        let mut checksum: Checksum = Checksum::new();
+        checksum.add_ipv4_pseudo_header(
+            &ip_header.source(),
+            &ip_header.destination(),
+            IpProtocol::Udp,
+            datagram.len() as u16,
+        );
+
+        finish(checksum, datagram)
+    }
+    /// computes checksum of datagram with ipv6 pseudo header, current checksum field is ignored
+    pub fn compute_checksum_ipv6(ip_header: &Ipv6Header, datagram: &[u8]) -> u16 {
+        let mut checksum: Checksum = Checksum::new();
+        checksum.add_ipv6_pseudo_header(
+            &ip_header.source(),
+            &ip_header.destination(),
+            IpProtocol::Udp,
+            datagram.len() as u32,
+        );
+
+        finish(checksum, datagram)
+    }
+    /// verifies checksum of datagram carried over ipv4, zero checksum means that it wasn't computed
+    pub fn verify_ipv4(ip_header: &Ipv4Header, datagram: &[u8]) -> bool {
+        match stored_checksum(datagram) {
+            Some(0) => true,
+            Some(check) => Self::compute_checksum_ipv4(ip_header, datagram) == check,
+            None => false,
+        }
+    }
+    /// verifies checksum of datagram carried over ipv6, where zero checksum is not allowed
+    pub fn verify_ipv6(ip_header: &Ipv6Header, datagram: &[u8]) -> bool {
+        match stored_checksum(datagram) {
+            Some(0) | None => false,
+            Some(check) => Self::compute_checksum_ipv6(ip_header, datagram) == check,
+        }
+    }
+}
+
+/// sums datagram with zeroed checksum field, zero result is sent as `0xffff` (RFC 768)
+fn finish(mut checksum: Checksum, datagram: &[u8]) -> u16 {
+    if datagram.len() >= UDP_HEADER_SIZE {
+        checksum
+            .add_bytes(&datagram[..CHECKSUM_OFFSET])
+            .add_u16(0)
+            .add_bytes(&datagram[CHECKSUM_OFFSET + 2..]);
+    } else {
+        checksum.add_bytes(datagram);
+    }
+
+    match checksum.finish() {
+        0 => 0xffff,
+        check => check,
+    }
+}
+
+fn stored_checksum(datagram: &[u8]) -> Option<u16> {
+    UdpHeader::ref_from(datagram).ok().map(|(header, _)| header.checksum())
+}
+
+impl<'a> UdpHeaderBuilder<'a> {
+    /// creates builder of datagram carried over ipv4, checksum uses ipv4 pseudo header
+    pub fn ipv4(source_port: u16, destination_port: u16, source: Ipv4, destination: Ipv4) -> Self {
+        Self::new(source_port, destination_port, PseudoHeader::Ipv4(source, destination))
+    }
+    /// creates builder of datagram carried over ipv6, checksum uses ipv6 pseudo header
+    pub fn ipv6(source_port: u16, destination_port: u16, source: Ipv6, destination: Ipv6) -> Self {
+        Self::new(source_port, destination_port, PseudoHeader::Ipv6(source, destination))
+    }
+    pub fn payload(mut self, payload: &'a [u8]) -> Self {
+        self.payload = payload;
+        self
+    }
+    pub fn total_len(&self) -> usize {
+        UDP_HEADER_SIZE + self.payload.len()
+    }
+    /// writes whole datagram into the buffer and returns its length
+    pub fn write(&self, buffer: &mut [u8]) -> Result<usize, BuildError> {
+        let total_len: usize = self.total_len();
+
+        if total_len > u16::MAX as usize {
+            return Err(BuildError::PayloadTooLarge(self.payload.len()));
+        }
+        if buffer.len() < total_len {
+            return Err(BuildError::BufferTooSmall {
+                needed: total_len,
+                available: buffer.len(),
+            });
+        }
+
+        let header: UdpHeader = UdpHeader {
+            source: U16Be::new(self.source_port),
+            dest: U16Be::new(self.destination_port),
+            len: U16Be::new(total_len as u16),
+            check: U16Be::new(0),
+        };
+
+        buffer[..UDP_HEADER_SIZE].copy_from_slice(header.as_bytes());
+        buffer[UDP_HEADER_SIZE..total_len].copy_from_slice(self.payload);
+
+        let mut checksum: Checksum = Checksum::new();
+        match &self.pseudo_header {
+            PseudoHeader::Ipv4(source, destination) => {
+                checksum.add_ipv4_pseudo_header(source, destination, IpProtocol::Udp, total_len as u16)
+            }
+            PseudoHeader::Ipv6(source, destination) => {
+                checksum.add_ipv6_pseudo_header(source, destination, IpProtocol::Udp, total_len as u32)
+            }
+        };
+
+        let check: u16 = finish(checksum, &buffer[..total_len]);
+        buffer[CHECKSUM_OFFSET..CHECKSUM_OFFSET + 2].copy_from_slice(&check.to_be_bytes());
+
+        Ok(total_len)
+    }
+    pub fn build(&self) -> Result<Vec<u8>, BuildError> {
+        let mut datagram: Vec<u8> = vec![0; self.total_len()];
+        self.write(&mut datagram)?;
+
+        Ok(datagram)
+    }
+    fn new(source_port: u16, destination_port: u16, pseudo_header: PseudoHeader) -> Self {
+        Self {
+            source_port,
+            destination_port,
+            pseudo_header,
+            payload: &[],
+        }
+    }
+}